@@ -0,0 +1,390 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+use uwuifyy_core::{
+    parse_seed, AlgorithmVersion, Category, InputMode, Language, PresetFormat, Seeding,
+    Segmentation, UwUConfig, UwUify, Whitespace,
+};
+
+const USAGE: &str = "\
+Usage: uwuify [OPTIONS] [FILE]...
+
+UwUifies each FILE (or standard input when none are given, or FILE is -)
+and writes the result to standard output. Options given later override
+earlier ones, including the ones loaded from a preset, and a preset only
+overrides the options before it that it has a setting for.
+
+Options:
+  -o, --output <FILE>        Write to FILE instead of standard output
+  -p, --preset <FILE>        Load settings from a JSON or TOML preset
+      --save-preset <FILE>   Save the settings as a JSON or TOML preset and exit
+  -w, --words <0..1>         Probability of UwUifying a word [default: 1]
+  -f, --faces <0..1>         Probability of adding a face [default: 0.05]
+  -a, --actions <0..1>       Probability of adding an action [default: 0.125]
+  -s, --stutters <0..1>      Probability of stuttering a word [default: 0.225]
+      --sentence-faces <0..1>
+                             Probability of adding a face at the end of a
+                             sentence or after a comma [default: 0]
+      --sentence-actions <0..1>
+                             Probability of adding an action between two
+                             sentences [default: 0]
+      --cooldown <WORDS>     Fewest words between two words with faces or
+                             actions [default: 0]
+      --burst <WORDS>        Most words in a row with faces or actions before
+                             the cooldown [default: 1]
+      --max-per-sentence <N> Most faces and actions in a sentence
+      --max-per-paragraph <N>
+                             Most faces and actions in a paragraph
+      --categories <LIST>    Kinds of faces and actions to pick from, separated
+                             by commas: happy, sad, angry, cat, shy and nsfw
+                             [default: all of them]
+      --sfw                  Never pick NSFW faces and actions
+      --sentiment            Pick faces and actions that match the mood of each
+                             sentence more often
+      --seed <SEED>          Seed to UwUify with, a number or any text [default: 69]
+      --algorithm <VERSION>  Version of the UwUifying rules to use [default: v8]
+  -l, --language <LANGUAGE>  Language of the text: auto, english, spanish, german,
+                             russian or japanese [default: auto]
+      --no-builtin-rules     Only use the rules from the preset to UwUify words
+      --no-builtin-dictionary
+                             Don't replace whole words from the built-in
+                             dictionary, only the ones from the preset
+      --seeding <SEEDING>    What each word is seeded with: word, position (the
+                             word and where it is) or context (the word and its
+                             neighbours) [default: word]
+      --segmentation <SEGMENTATION>
+                             Where words are split: spaces, or unicode to also
+                             split Chinese and Japanese into words. Thai, Lao,
+                             Khmer and Burmese are only split at spaces
+                             [default: spaces]
+  -m, --mode <MODE>          Input format: plain, markdown or html [default: plain]
+      --passes <N>           How many times to UwUify the text, each pass only
+                             adding to the ones before it [default: 1]
+  -d, --deuwuify             Turn UwUified text back into plain text, as closely
+                             as it can be told apart when it was UwUified with
+                             the same settings and seed
+      --collapse-whitespace  Join all the words with single spaces
+  -h, --help                 Print this help
+  -V, --version              Print the version
+";
+
+struct Args {
+    uwuify: UwUify,
+    output: Option<String>,
+    save_preset: Option<String>,
+    deuwuify: bool,
+    files: Vec<String>,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut config = UwUConfig::builder().whitespace(Whitespace::Preserve);
+
+    let mut output = None;
+    let mut save_preset = None;
+    let mut deuwuify = false;
+    let mut files = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_owned(), Some(value.to_owned()))
+            }
+            _ => (arg.clone(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{flag} needs a value"))
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" => {
+                print!("{USAGE}");
+                return Ok(None);
+            }
+            "-V" | "--version" => {
+                println!("uwuify {}", env!("CARGO_PKG_VERSION"));
+                return Ok(None);
+            }
+            "-o" | "--output" => output = Some(value()?),
+            "-p" | "--preset" => {
+                let path = value()?;
+                let preset = fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+                config = UwUConfig::from_preset_over(
+                    &preset,
+                    PresetFormat::from_file_name(&path),
+                    &config.clone().build(),
+                )
+                .map_err(|e| format!("{path}: {e}"))?
+                .to_builder();
+            }
+            "--save-preset" => save_preset = Some(value()?),
+            "-w" | "--words" => config = config.words(probability(&flag, &value()?)?),
+            "-f" | "--faces" => config = config.faces(probability(&flag, &value()?)?),
+            "-a" | "--actions" => config = config.actions(probability(&flag, &value()?)?),
+            "-s" | "--stutters" => config = config.stutters(probability(&flag, &value()?)?),
+            "--sentence-faces" => {
+                config = config.sentence_faces(probability(&flag, &value()?)?);
+            }
+            "--sentence-actions" => {
+                config = config.sentence_actions(probability(&flag, &value()?)?);
+            }
+            "--cooldown" => config = config.cooldown(count(&flag, &value()?)?),
+            "--burst" => config = config.burst(count(&flag, &value()?)?),
+            "--max-per-sentence" => {
+                config = config.max_per_sentence(Some(count(&flag, &value()?)?));
+            }
+            "--max-per-paragraph" => {
+                config = config.max_per_paragraph(Some(count(&flag, &value()?)?));
+            }
+            "--categories" => {
+                let categories = value()?;
+                config = config.categories(
+                    categories
+                        .split(',')
+                        .map(|category| {
+                            category
+                                .trim()
+                                .parse::<Category>()
+                                .map_err(|_| format!("unknown category `{category}`"))
+                        })
+                        .collect::<Result<_, _>>()?,
+                );
+            }
+            "--sfw" => config = config.sfw(true),
+            "--sentiment" => config = config.sentiment(true),
+            "--seed" => config = config.seed(parse_seed(&value()?)),
+            "--algorithm" => {
+                let version = value()?;
+                config = config.algorithm(
+                    version
+                        .parse::<AlgorithmVersion>()
+                        .map_err(|_| format!("unknown algorithm version `{version}`"))?,
+                );
+            }
+            "-l" | "--language" => {
+                let language = value()?;
+                config = config.language(
+                    language
+                        .parse::<Language>()
+                        .map_err(|_| format!("unknown language `{language}`"))?,
+                );
+            }
+            "--no-builtin-rules" => config = config.builtin_rules(false),
+            "--no-builtin-dictionary" => config = config.builtin_dictionary(false),
+            "--seeding" => {
+                let seeding = value()?;
+                config = config.seeding(
+                    seeding
+                        .parse::<Seeding>()
+                        .map_err(|_| format!("unknown seeding `{seeding}`"))?,
+                );
+            }
+            "--segmentation" => {
+                let segmentation = value()?;
+                config = config.segmentation(
+                    segmentation
+                        .parse::<Segmentation>()
+                        .map_err(|_| format!("unknown segmentation `{segmentation}`"))?,
+                );
+            }
+            "-m" | "--mode" => {
+                let mode = value()?;
+                config = config.mode(match mode.to_ascii_lowercase().as_str() {
+                    "plain" => InputMode::Plain,
+                    "markdown" | "md" => InputMode::Markdown,
+                    "html" => InputMode::Html,
+                    _ => return Err(format!("unknown mode `{mode}`")),
+                });
+            }
+            "--passes" => config = config.passes(count(&flag, &value()?)?),
+            "--collapse-whitespace" => config = config.whitespace(Whitespace::Collapse),
+            "-d" | "--deuwuify" => deuwuify = true,
+            "--" => files.extend(args.by_ref()),
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown option `{arg}`"))
+            }
+            _ => files.push(arg),
+        }
+    }
+
+    Ok(Some(Args {
+        uwuify: UwUify::from_config(config.build()),
+        output,
+        save_preset,
+        deuwuify,
+        files,
+    }))
+}
+
+fn probability(flag: &str, value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(p) if (0.0..=1.0).contains(&p) => Ok(p),
+        _ => Err(format!(
+            "{flag} must be a number between 0 and 1, got `{value}`"
+        )),
+    }
+}
+
+fn count(flag: &str, value: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{flag} must be a whole number, got `{value}`"))
+}
+
+fn read_input(file: &str) -> io::Result<String> {
+    if file == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(file)
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => return ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("uwuify: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Some(path) = &args.save_preset {
+        let preset = args
+            .uwuify
+            .config()
+            .to_preset(PresetFormat::from_file_name(path));
+
+        return match fs::write(path, preset) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("uwuify: {path}: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let files = if args.files.is_empty() {
+        vec!["-".to_owned()]
+    } else {
+        args.files
+    };
+
+    let mut output = String::new();
+    for file in &files {
+        let input = match read_input(file) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("uwuify: {file}: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        if args.deuwuify {
+            args.uwuify.deuwuify(&input, &mut output).unwrap();
+        } else {
+            args.uwuify.uwuify_sentence(&input, &mut output).unwrap();
+        }
+    }
+
+    let written = match &args.output {
+        Some(path) => fs::write(path, output),
+        None => io::stdout().lock().write_all(output.as_bytes()),
+    };
+
+    if let Err(e) = written {
+        eprintln!(
+            "uwuify: {}: {e}",
+            args.output.as_deref().unwrap_or("standard output")
+        );
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use uwuifyy_core::{UwUConfig, Whitespace};
+
+    use super::Args;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        super::parse_args(args.iter().map(|arg| arg.to_string())).map(Option::unwrap)
+    }
+
+    fn config(args: &[&str]) -> UwUConfig {
+        parse(args).unwrap().uwuify.config().clone()
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(config(&["--faces=0.5"]).faces(), 0.5);
+        assert_eq!(config(&["-f", "0.5"]).faces(), 0.5);
+        assert_eq!(config(&["--faces", "1", "-f", "0"]).faces(), 0.0);
+
+        let args = parse(&["-d", "a.txt", "--", "-b.txt"]).unwrap();
+        assert!(args.deuwuify);
+        assert_eq!(args.files, ["a.txt", "-b.txt"]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse(&["--faces", "2"]).err().as_deref(),
+            Some("--faces must be a number between 0 and 1, got `2`")
+        );
+        assert_eq!(
+            parse(&["--uwu"]).err().as_deref(),
+            Some("unknown option `--uwu`")
+        );
+        assert_eq!(
+            parse(&["--seed"]).err().as_deref(),
+            Some("--seed needs a value")
+        );
+    }
+
+    #[test]
+    fn presets() {
+        let dir = std::env::temp_dir().join(format!("uwuify-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str, preset: &str| {
+            let path = dir.join(name);
+            fs::write(&path, preset).unwrap();
+            path.to_str().unwrap().to_owned()
+        };
+
+        // Whitespace is only collapsed when the preset says so
+        let words = path("words.toml", "version = 1\nwords = 0.5\n");
+        let loaded = config(&["--preset", &words]);
+        assert_eq!(loaded.words(), 0.5);
+        assert_eq!(loaded.whitespace(), Whitespace::Preserve);
+
+        let collapse = path(
+            "collapse.json",
+            "{\"version\": 1, \"whitespace\": \"collapse\"}",
+        );
+        assert_eq!(
+            config(&["-p", &collapse]).whitespace(),
+            Whitespace::Collapse
+        );
+
+        // Later options still override the preset, and earlier ones are only overridden by
+        // what's in it
+        assert_eq!(config(&["-p", &words, "-w", "1"]).words(), 1.0);
+        let loaded = config(&["--seed", "5", "-f", "1", "-w", "1", "-p", &words]);
+        assert_eq!(loaded.seed(), 5);
+        assert_eq!(loaded.faces(), 1.0);
+        assert_eq!(loaded.words(), 0.5);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,364 @@
+use std::fmt::Write;
+
+use crate::constants::{ACTIONS, DICTIONARY, MIXED_FACES};
+use crate::language::Language;
+use crate::packs::Decoration;
+use crate::rules::{Case, Rule};
+
+/// Which version of the UwUifying rules and tables to use.
+///
+/// Once a version is released, its rules and tables never change again, so a saved config and
+/// seed keep giving exactly the same text. New behavior always goes into a new version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum AlgorithmVersion {
+    /// The original rules: L and R become W, N followed by a vowel becomes Ny, and faces and
+    /// actions are picked from `MIXED_FACES` and `ACTIONS`. A single random number per word
+    /// decides every effect, so they all happen together
+    V1,
+    /// The V1 rules and tables, but faces, actions, stutters and UwUifying the word each get
+    /// their own random number
+    V2,
+    /// V2, plus whole words that are replaced from `DICTIONARY` before the rules get to them
+    V3,
+    /// V3, but punctuation around a word is kept out of the way: the stutter goes on the first
+    /// letter, the rules and dictionary only see the word, and punctuation on its own is left
+    /// alone
+    V4,
+    /// V4, but working on whole grapheme clusters instead of code points: stutters repeat the
+    /// whole first letter with its accents, and rules never split accents or emoji sequences
+    V5,
+    /// V5, plus rules for Spanish, German, Russian and Japanese, picked by
+    /// [`Language`](crate::Language)
+    V6,
+    /// V6, but faces and actions are picked by their weights instead of all being as likely,
+    /// so the classics like `OwO` come up more and the suggestive ones less
+    V7,
+    /// V7, but faces, actions and stutters that are already in the text are left alone, so
+    /// UwUifying something twice doesn't stack them
+    V8,
+}
+
+impl Default for AlgorithmVersion {
+    fn default() -> Self {
+        Self::LATEST
+    }
+}
+
+impl AlgorithmVersion {
+    pub const ALL: &'static [AlgorithmVersion] = &[
+        AlgorithmVersion::V1,
+        AlgorithmVersion::V2,
+        AlgorithmVersion::V3,
+        AlgorithmVersion::V4,
+        AlgorithmVersion::V5,
+        AlgorithmVersion::V6,
+        AlgorithmVersion::V7,
+        AlgorithmVersion::V8,
+    ];
+    pub const LATEST: AlgorithmVersion = AlgorithmVersion::V8;
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AlgorithmVersion::V1 => "v1",
+            AlgorithmVersion::V2 => "v2",
+            AlgorithmVersion::V3 => "v3",
+            AlgorithmVersion::V4 => "v4",
+            AlgorithmVersion::V5 => "v5",
+            AlgorithmVersion::V6 => "v6",
+            AlgorithmVersion::V7 => "v7",
+            AlgorithmVersion::V8 => "v8",
+        }
+    }
+
+    pub fn faces(self) -> &'static [Decoration] {
+        match self {
+            AlgorithmVersion::V1
+            | AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5
+            | AlgorithmVersion::V6
+            | AlgorithmVersion::V7
+            | AlgorithmVersion::V8 => &MIXED_FACES,
+        }
+    }
+
+    pub fn actions(self) -> &'static [Decoration] {
+        match self {
+            AlgorithmVersion::V1
+            | AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5
+            | AlgorithmVersion::V6
+            | AlgorithmVersion::V7
+            | AlgorithmVersion::V8 => &ACTIONS,
+        }
+    }
+
+    /// Whether every effect gets its own random number, instead of sharing one
+    pub(crate) fn independent_draws(self) -> bool {
+        match self {
+            AlgorithmVersion::V1 => false,
+            AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5
+            | AlgorithmVersion::V6
+            | AlgorithmVersion::V7
+            | AlgorithmVersion::V8 => true,
+        }
+    }
+
+    /// Whether faces and actions are picked by their [weights](Decoration::weight), older
+    /// versions pick every one of them as often
+    pub(crate) fn weighted(self) -> bool {
+        match self {
+            AlgorithmVersion::V1
+            | AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5
+            | AlgorithmVersion::V6 => false,
+            AlgorithmVersion::V7 | AlgorithmVersion::V8 => true,
+        }
+    }
+
+    /// Whether faces, actions and stutters that are already in the text are left as they are
+    pub(crate) fn keeps_decorations(self) -> bool {
+        match self {
+            AlgorithmVersion::V1
+            | AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5
+            | AlgorithmVersion::V6
+            | AlgorithmVersion::V7 => false,
+            AlgorithmVersion::V8 => true,
+        }
+    }
+
+    /// Whole words that are replaced instead of going through the rules, sorted by the word
+    pub fn dictionary(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            AlgorithmVersion::V1 | AlgorithmVersion::V2 => &[],
+            AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5
+            | AlgorithmVersion::V6
+            | AlgorithmVersion::V7
+            | AlgorithmVersion::V8 => &DICTIONARY,
+        }
+    }
+
+    /// Whether punctuation at the start and end of a word is kept apart from it
+    pub(crate) fn separates_punctuation(self) -> bool {
+        match self {
+            AlgorithmVersion::V1 | AlgorithmVersion::V2 | AlgorithmVersion::V3 => false,
+            AlgorithmVersion::V4
+            | AlgorithmVersion::V5
+            | AlgorithmVersion::V6
+            | AlgorithmVersion::V7
+            | AlgorithmVersion::V8 => true,
+        }
+    }
+
+    /// Whether words are handled as grapheme clusters rather than code points
+    pub(crate) fn graphemes(self) -> bool {
+        match self {
+            AlgorithmVersion::V1
+            | AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4 => false,
+            AlgorithmVersion::V5
+            | AlgorithmVersion::V6
+            | AlgorithmVersion::V7
+            | AlgorithmVersion::V8 => true,
+        }
+    }
+
+    /// The built-in rules for words in `language`, older versions only have English ones
+    pub fn language_rules(self, language: Language) -> Vec<Rule> {
+        match self {
+            AlgorithmVersion::V1
+            | AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5 => self.rules(),
+            AlgorithmVersion::V6 | AlgorithmVersion::V7 | AlgorithmVersion::V8 => match language {
+                Language::Auto | Language::English => self.rules(),
+                language => language.rules_v6(),
+            },
+        }
+    }
+
+    /// Whether there are rules for other languages than English, and [`Language::Auto`] picks
+    /// the language of each word
+    pub(crate) fn language_packs(self) -> bool {
+        match self {
+            AlgorithmVersion::V1
+            | AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5 => false,
+            AlgorithmVersion::V6 | AlgorithmVersion::V7 | AlgorithmVersion::V8 => true,
+        }
+    }
+
+    /// The built-in rules as a rule set, for using them alongside custom rules. They give
+    /// exactly the same text as [`AlgorithmVersion::uwuify_word`]
+    pub fn rules(self) -> Vec<Rule> {
+        let pattern = |find, replace| Rule::pattern(find, replace).unwrap();
+
+        match self {
+            AlgorithmVersion::V1
+            | AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5
+            | AlgorithmVersion::V6
+            | AlgorithmVersion::V7
+            | AlgorithmVersion::V8 => vec![
+                pattern("([Nn])([AEIOUaeiou])", "${1}y$2"),
+                pattern("([Nn])[LR]", "${1}W"),
+                pattern("([Nn])[lr]", "${1}w"),
+                // Whatever follows an N is never the start of another rule
+                pattern("[Nn].", "$0"),
+                Rule::literal("l", "w").with_case(Case::Preserve),
+                Rule::literal("r", "w").with_case(Case::Preserve),
+            ],
+        }
+    }
+
+    /// Writes the UwUified version of `word` into `out`
+    pub(crate) fn uwuify_word(self, word: &str, out: &mut String) {
+        match self {
+            AlgorithmVersion::V1
+            | AlgorithmVersion::V2
+            | AlgorithmVersion::V3
+            | AlgorithmVersion::V4
+            | AlgorithmVersion::V5
+            | AlgorithmVersion::V6
+            | AlgorithmVersion::V7
+            | AlgorithmVersion::V8 => uwuify_word_v1(word, out),
+        }
+    }
+}
+
+impl std::str::FromStr for AlgorithmVersion {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AlgorithmVersion::ALL
+            .iter()
+            .copied()
+            .find(|version| version.name().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+fn uwuify_word_v1(word: &str, out: &mut String) {
+    let mut chars = word.chars();
+
+    while let Some(w) = chars.next() {
+        match w {
+            'L' | 'R' => out.write_char('W').unwrap(),
+            'l' | 'r' => out.write_char('w').unwrap(),
+            c @ ('N' | 'n') => {
+                out.write_char(c).unwrap();
+                if let Some(w) = chars.next() {
+                    match w {
+                        'L' | 'R' => out.write_char('W').unwrap(),
+                        'l' | 'r' => out.write_char('w').unwrap(),
+                        c @ ('A' | 'E' | 'I' | 'O' | 'U' | 'a' | 'e' | 'i' | 'o' | 'u') => {
+                            out.write_char('y').unwrap();
+                            out.write_char(c).unwrap();
+                        }
+                        c => out.write_char(c).unwrap(),
+                    }
+                }
+            }
+            c => out.write_char(c).unwrap(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AlgorithmVersion;
+    use crate::rules;
+
+    fn uwuify_word(version: AlgorithmVersion, word: &str) -> String {
+        let mut out = String::new();
+        version.uwuify_word(word, &mut out);
+        out
+    }
+
+    #[test]
+    fn v1_rules() {
+        let v1 = AlgorithmVersion::V1;
+        assert_eq!(uwuify_word(v1, "Really"), "Weawwy");
+        assert_eq!(uwuify_word(v1, "NOTHING"), "NyOTHING");
+        assert_eq!(uwuify_word(v1, "inner"), "innew");
+        assert_eq!(uwuify_word(v1, "only"), "onwy");
+    }
+
+    #[test]
+    fn builtin_rule_sets() {
+        let words = [
+            "Really",
+            "NOTHING",
+            "inner",
+            "only",
+            "nna",
+            "nnLa",
+            "Nl",
+            "nR",
+            "n",
+            "N.",
+            "ñandu",
+            "Lörem",
+            "Snail,",
+            "fLuRRy",
+            "nyan~",
+            "",
+            // Combining accents and an emoji sequence
+            "ne\u{301}e",
+            "n\u{303}a",
+            "l\u{301}r",
+            "Nl\u{301}",
+            "n👩\u{200d}💻l",
+        ];
+
+        for version in AlgorithmVersion::ALL {
+            let rule_set = rules::rule_set(&[], version.rules());
+            for word in words {
+                let mut out = String::new();
+                rules::apply(&rule_set, word, version.graphemes(), &mut out);
+                assert_eq!(out, uwuify_word(*version, word), "{version:?} {word}");
+            }
+        }
+    }
+
+    #[test]
+    fn v1_tables() {
+        let v1 = AlgorithmVersion::V1;
+        assert_eq!(v1.faces().len(), 89);
+        assert_eq!(v1.faces()[0].text(), "OwO ");
+        assert_eq!(v1.faces()[88].text(), "(　･ω･)☞ ");
+        assert_eq!(v1.actions().len(), 19);
+        assert_eq!(v1.actions()[0].text(), "*notices bulge* ");
+        assert_eq!(v1.actions()[18].text(), "*bites lips* ");
+    }
+
+    #[test]
+    fn names() {
+        for version in AlgorithmVersion::ALL {
+            assert_eq!(version.name().parse(), Ok(*version));
+        }
+        assert_eq!("V1".parse(), Ok(AlgorithmVersion::V1));
+        assert_eq!(AlgorithmVersion::default(), AlgorithmVersion::LATEST);
+    }
+}
@@ -0,0 +1,541 @@
+use std::collections::BTreeMap;
+
+use crate::algorithm::AlgorithmVersion;
+use crate::language::Language;
+use crate::packs::{Category, Decoration};
+use crate::rules::Rule;
+use crate::uwu::{InputMode, Seeding, Segmentation, Whitespace};
+
+/// The seed UwUify starts out with
+pub const DEFAULT_SEED: u64 = 69;
+
+/// Picks a new seed at random. These are kept below 2^32, so they're at most 10 digits long
+/// and easy to share
+pub fn random_seed() -> u64 {
+    let mut seed = [0u8; 4];
+    getrandom::getrandom(&mut seed).unwrap();
+    u32::from_le_bytes(seed).into()
+}
+
+/// Turns text into a seed, so anything can be shared as one. Numbers are used as they are,
+/// anything else (a word, a phrase, an emoji) becomes the SipHash-2-4 of its UTF-8 bytes keyed
+/// with `(0, 0)`. Leading and trailing whitespace is ignored
+pub fn parse_seed(seed: &str) -> u64 {
+    let seed = seed.trim();
+    seed.parse().unwrap_or_else(|_| {
+        let mut hasher = siphasher::sip::SipHasher24::new_with_keys(0, 0);
+        std::hash::Hasher::write(&mut hasher, seed.as_bytes());
+        std::hash::Hasher::finish(&hasher)
+    })
+}
+
+/// Everything that decides how text gets UwUified. Probabilities are always in `0.0..=1.0`,
+/// use [`UwUConfig::builder`] to make one
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct UwUConfig {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "serde_probability"))]
+    words: f64,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "serde_probability"))]
+    faces: f64,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "serde_probability"))]
+    actions: f64,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "serde_probability"))]
+    stutters: f64,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "serde_probability"))]
+    sentence_faces: f64,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "serde_probability"))]
+    sentence_actions: f64,
+    cooldown: u32,
+    burst: u32,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    max_per_sentence: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    max_per_paragraph: Option<u32>,
+    /// Faces and actions are only picked from these
+    categories: Vec<Category>,
+    sfw: bool,
+    /// Whether faces and actions lean towards the mood of their sentence
+    sentiment: bool,
+    /// Whether the faces of `algorithm` are used alongside `custom_faces`
+    builtin_faces: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    custom_faces: Vec<Decoration>,
+    /// Whether the actions of `algorithm` are used alongside `custom_actions`
+    builtin_actions: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    custom_actions: Vec<Decoration>,
+    #[cfg_attr(feature = "serde", serde(with = "serde_seed"))]
+    seed: u64,
+    /// Presets from before there were versions were all made with V1
+    #[cfg_attr(feature = "serde", serde(default = "legacy_algorithm"))]
+    algorithm: AlgorithmVersion,
+    language: Language,
+    /// Whether the rules of `algorithm` are used alongside `rules`
+    builtin_rules: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    rules: Vec<Rule>,
+    /// Whether the dictionary of `algorithm` is used alongside `dictionary`
+    builtin_dictionary: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "BTreeMap::is_empty"))]
+    dictionary: BTreeMap<String, String>,
+    seeding: Seeding,
+    whitespace: Whitespace,
+    segmentation: Segmentation,
+    mode: InputMode,
+    passes: u32,
+}
+
+impl Default for UwUConfig {
+    fn default() -> Self {
+        Self {
+            words: 1.0,
+            faces: 0.05,
+            actions: 0.125,
+            stutters: 0.225,
+            sentence_faces: 0.0,
+            sentence_actions: 0.0,
+            cooldown: 0,
+            burst: 1,
+            max_per_sentence: None,
+            max_per_paragraph: None,
+            categories: Category::ALL.to_vec(),
+            sfw: false,
+            sentiment: false,
+            builtin_faces: true,
+            custom_faces: Vec::new(),
+            builtin_actions: true,
+            custom_actions: Vec::new(),
+            seed: DEFAULT_SEED,
+            algorithm: AlgorithmVersion::default(),
+            language: Language::default(),
+            builtin_rules: true,
+            rules: Vec::new(),
+            builtin_dictionary: true,
+            dictionary: BTreeMap::new(),
+            seeding: Seeding::default(),
+            whitespace: Whitespace::default(),
+            segmentation: Segmentation::default(),
+            mode: InputMode::default(),
+            passes: 1,
+        }
+    }
+}
+
+impl UwUConfig {
+    pub fn builder() -> UwUConfigBuilder {
+        UwUConfigBuilder(Self::default())
+    }
+
+    /// Starts a builder from this config, to change some of its settings
+    pub fn to_builder(&self) -> UwUConfigBuilder {
+        UwUConfigBuilder(self.clone())
+    }
+
+    /// Probability of a word being UwUified
+    pub fn words(&self) -> f64 {
+        self.words
+    }
+
+    /// Probability of a face being put before a word
+    pub fn faces(&self) -> f64 {
+        self.faces
+    }
+
+    /// Probability of an action being put before a word
+    pub fn actions(&self) -> f64 {
+        self.actions
+    }
+
+    /// Probability of a word stuttering
+    pub fn stutters(&self) -> f64 {
+        self.stutters
+    }
+
+    /// Probability of a face being put after a word that ends a clause or a sentence
+    pub fn sentence_faces(&self) -> f64 {
+        self.sentence_faces
+    }
+
+    /// Probability of an action being put between two sentences
+    pub fn sentence_actions(&self) -> f64 {
+        self.sentence_actions
+    }
+
+    /// Fewest words without faces or actions between two words with them
+    pub fn cooldown(&self) -> u32 {
+        self.cooldown
+    }
+
+    /// Most words in a row that can have faces or actions before the cooldown starts
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Most faces and actions in one sentence, `None` for no limit
+    pub fn max_per_sentence(&self) -> Option<u32> {
+        self.max_per_sentence
+    }
+
+    /// Most faces and actions in one paragraph, `None` for no limit
+    pub fn max_per_paragraph(&self) -> Option<u32> {
+        self.max_per_paragraph
+    }
+
+    /// The kinds of faces and actions that can be picked
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Whether NSFW faces and actions are left out, even when their category is turned on
+    pub fn sfw(&self) -> bool {
+        self.sfw
+    }
+
+    /// Whether sentences are scored as happy, sad or angry with a small built-in list of words,
+    /// making faces and actions of the same category more likely to be picked in them
+    pub fn sentiment(&self) -> bool {
+        self.sentiment
+    }
+
+    /// Whether the built-in faces of the algorithm are used
+    pub fn builtin_faces(&self) -> bool {
+        self.builtin_faces
+    }
+
+    /// Custom faces, picked alongside or instead of the built-in ones
+    pub fn custom_faces(&self) -> &[Decoration] {
+        &self.custom_faces
+    }
+
+    /// Whether the built-in actions of the algorithm are used
+    pub fn builtin_actions(&self) -> bool {
+        self.builtin_actions
+    }
+
+    /// Custom actions, picked alongside or instead of the built-in ones
+    pub fn custom_actions(&self) -> &[Decoration] {
+        &self.custom_actions
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn algorithm(&self) -> AlgorithmVersion {
+        self.algorithm
+    }
+
+    /// Which language's built-in rules are used, since V6
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Whether the built-in rules of the algorithm are used
+    pub fn builtin_rules(&self) -> bool {
+        self.builtin_rules
+    }
+
+    /// Custom rules, used alongside or instead of the built-in ones
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Whether the built-in dictionary of the algorithm is used
+    pub fn builtin_dictionary(&self) -> bool {
+        self.builtin_dictionary
+    }
+
+    /// Custom whole word replacements, these win over the built-in ones
+    pub fn dictionary(&self) -> &BTreeMap<String, String> {
+        &self.dictionary
+    }
+
+    pub fn seeding(&self) -> Seeding {
+        self.seeding
+    }
+
+    pub fn whitespace(&self) -> Whitespace {
+        self.whitespace
+    }
+
+    /// How text is split into words, besides at whitespace
+    pub fn segmentation(&self) -> Segmentation {
+        self.segmentation
+    }
+
+    pub fn mode(&self) -> InputMode {
+        self.mode
+    }
+
+    /// How many times the text is UwUified, each pass after the first with its own seed. Since
+    /// V8 a pass leaves what the ones before it added alone, so more passes only add more
+    pub fn passes(&self) -> u32 {
+        self.passes
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UwUConfigBuilder(UwUConfig);
+
+impl UwUConfigBuilder {
+    pub fn words(mut self, words: f64) -> Self {
+        self.0.words = probability(words);
+        self
+    }
+
+    pub fn faces(mut self, faces: f64) -> Self {
+        self.0.faces = probability(faces);
+        self
+    }
+
+    pub fn actions(mut self, actions: f64) -> Self {
+        self.0.actions = probability(actions);
+        self
+    }
+
+    pub fn stutters(mut self, stutters: f64) -> Self {
+        self.0.stutters = probability(stutters);
+        self
+    }
+
+    pub fn sentence_faces(mut self, sentence_faces: f64) -> Self {
+        self.0.sentence_faces = probability(sentence_faces);
+        self
+    }
+
+    pub fn sentence_actions(mut self, sentence_actions: f64) -> Self {
+        self.0.sentence_actions = probability(sentence_actions);
+        self
+    }
+
+    pub fn cooldown(mut self, cooldown: u32) -> Self {
+        self.0.cooldown = cooldown;
+        self
+    }
+
+    /// At least 1, which means every word with faces or actions is followed by the cooldown
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.0.burst = burst.max(1);
+        self
+    }
+
+    pub fn max_per_sentence(mut self, max_per_sentence: Option<u32>) -> Self {
+        self.0.max_per_sentence = max_per_sentence;
+        self
+    }
+
+    pub fn max_per_paragraph(mut self, max_per_paragraph: Option<u32>) -> Self {
+        self.0.max_per_paragraph = max_per_paragraph;
+        self
+    }
+
+    pub fn categories(mut self, categories: Vec<Category>) -> Self {
+        self.0.categories = categories;
+        self
+    }
+
+    pub fn sfw(mut self, sfw: bool) -> Self {
+        self.0.sfw = sfw;
+        self
+    }
+
+    pub fn sentiment(mut self, sentiment: bool) -> Self {
+        self.0.sentiment = sentiment;
+        self
+    }
+
+    pub fn builtin_faces(mut self, builtin_faces: bool) -> Self {
+        self.0.builtin_faces = builtin_faces;
+        self
+    }
+
+    pub fn custom_faces(mut self, custom_faces: Vec<Decoration>) -> Self {
+        self.0.custom_faces = custom_faces;
+        self
+    }
+
+    pub fn custom_face(mut self, face: Decoration) -> Self {
+        self.0.custom_faces.push(face);
+        self
+    }
+
+    pub fn builtin_actions(mut self, builtin_actions: bool) -> Self {
+        self.0.builtin_actions = builtin_actions;
+        self
+    }
+
+    pub fn custom_actions(mut self, custom_actions: Vec<Decoration>) -> Self {
+        self.0.custom_actions = custom_actions;
+        self
+    }
+
+    pub fn custom_action(mut self, action: Decoration) -> Self {
+        self.0.custom_actions.push(action);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.0.seed = seed;
+        self
+    }
+
+    pub fn algorithm(mut self, algorithm: AlgorithmVersion) -> Self {
+        self.0.algorithm = algorithm;
+        self
+    }
+
+    pub fn language(mut self, language: Language) -> Self {
+        self.0.language = language;
+        self
+    }
+
+    pub fn builtin_rules(mut self, builtin_rules: bool) -> Self {
+        self.0.builtin_rules = builtin_rules;
+        self
+    }
+
+    pub fn rules(mut self, rules: Vec<Rule>) -> Self {
+        self.0.rules = rules;
+        self
+    }
+
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.0.rules.push(rule);
+        self
+    }
+
+    pub fn builtin_dictionary(mut self, builtin_dictionary: bool) -> Self {
+        self.0.builtin_dictionary = builtin_dictionary;
+        self
+    }
+
+    pub fn dictionary(mut self, dictionary: BTreeMap<String, String>) -> Self {
+        self.0.dictionary = dictionary;
+        self
+    }
+
+    /// Adds a custom whole word replacement
+    pub fn replace_word(mut self, word: impl Into<String>, replacement: impl Into<String>) -> Self {
+        self.0.dictionary.insert(word.into(), replacement.into());
+        self
+    }
+
+    pub fn seeding(mut self, seeding: Seeding) -> Self {
+        self.0.seeding = seeding;
+        self
+    }
+
+    pub fn whitespace(mut self, whitespace: Whitespace) -> Self {
+        self.0.whitespace = whitespace;
+        self
+    }
+
+    pub fn segmentation(mut self, segmentation: Segmentation) -> Self {
+        self.0.segmentation = segmentation;
+        self
+    }
+
+    pub fn mode(mut self, mode: InputMode) -> Self {
+        self.0.mode = mode;
+        self
+    }
+
+    pub fn passes(mut self, passes: u32) -> Self {
+        self.0.passes = passes.max(1);
+        self
+    }
+
+    pub fn build(self) -> UwUConfig {
+        self.0
+    }
+}
+
+/// Clamps `p` into `0.0..=1.0`, NaN counts as never
+fn probability(p: f64) -> f64 {
+    if p.is_nan() {
+        0.0
+    } else {
+        p.clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+fn legacy_algorithm() -> AlgorithmVersion {
+    AlgorithmVersion::V1
+}
+
+#[cfg(feature = "serde")]
+fn serde_probability<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    <f64 as serde::Deserialize>::deserialize(deserializer).map(probability)
+}
+
+/// Seeds are written as strings, TOML can't hold integers above `i64::MAX` and JavaScript
+/// can't hold them above 2^53 without losing precision
+#[cfg(feature = "serde")]
+mod serde_seed {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(seed: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(seed)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Seed {
+            Number(u64),
+            String(String),
+        }
+
+        Ok(match Seed::deserialize(deserializer)? {
+            Seed::Number(seed) => seed,
+            Seed::String(seed) => super::parse_seed(&seed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_seed, random_seed, UwUConfig};
+
+    #[test]
+    fn probabilities_are_clamped() {
+        let config = UwUConfig::builder()
+            .words(1.5)
+            .faces(-0.5)
+            .actions(f64::NAN)
+            .stutters(0.5)
+            .build();
+
+        assert_eq!(config.words(), 1.0);
+        assert_eq!(config.faces(), 0.0);
+        assert_eq!(config.actions(), 0.0);
+        assert_eq!(config.stutters(), 0.5);
+    }
+
+    #[test]
+    fn to_builder_keeps_settings() {
+        let config = UwUConfig::builder().faces(0.75).seed(420).build();
+        let changed = config.to_builder().words(0.5).build();
+
+        assert_eq!(changed.faces(), 0.75);
+        assert_eq!(changed.seed(), 420);
+        assert_eq!(changed.words(), 0.5);
+        assert_eq!(
+            UwUConfig::default().to_builder().build(),
+            UwUConfig::default()
+        );
+    }
+
+    #[test]
+    fn seeds() {
+        assert_eq!(parse_seed("69"), 69);
+        assert_eq!(parse_seed(" 18446744073709551615\n"), u64::MAX);
+        assert_eq!(parse_seed("senpai"), parse_seed(" senpai "));
+        assert_ne!(parse_seed("senpai"), parse_seed("Senpai"));
+        assert_eq!(parse_seed("senpai"), 0xb1ae_6b00_20c4_234c);
+        assert!(random_seed() <= u32::MAX.into());
+    }
+}
@@ -0,0 +1,499 @@
+//! The tables used by each [`AlgorithmVersion`](crate::AlgorithmVersion). These are frozen,
+//! changing them changes every output made with the versions that use them, so new tables go
+//! into new versions instead. That goes for the weights and categories of the faces and
+//! actions too, even though they only matter since V7 and when some categories are left out
+
+use crate::packs::Category::{self, Angry, Cat, Happy, Nsfw, Sad, Shy};
+use crate::packs::Decoration;
+
+pub static MIXED_FACES: [Decoration; 89] = [
+    Decoration::new("OwO ", 3, &[Happy]),
+    Decoration::new("UwU ", 3, &[Happy]),
+    Decoration::new(">w< ", 3, &[Happy, Shy]),
+    Decoration::new("^w^ ", 3, &[Happy]),
+    Decoration::new("^-^ ", 2, &[Happy]),
+    Decoration::new(":3 ", 3, &[Happy, Cat]),
+    Decoration::new("x3 ", 2, &[Happy, Cat]),
+    Decoration::new("xDD ", 2, &[Happy]),
+    Decoration::new(";;w;; ", 2, &[Sad]),
+    Decoration::new(">_< ", 1, &[Sad, Angry]),
+    Decoration::new(">_> ", 2, &[Shy]),
+    Decoration::new("^.^ ", 2, &[Happy]),
+    Decoration::new(":33 ", 2, &[Happy, Cat]),
+    Decoration::new("uWu ", 2, &[Happy]),
+    Decoration::new("(o^ ^o) ", 2, &[Happy]),
+    Decoration::new("(*^.^*) ", 2, &[Happy, Shy]),
+    Decoration::new("o(>< )o ", 1, &[Angry]),
+    Decoration::new("(>_<) ", 2, &[Sad]),
+    Decoration::new("~(>_<~) ", 2, &[Sad]),
+    Decoration::new("(;;;*_*) ", 2, &[Sad, Shy]),
+    Decoration::new("{{ (>_<) }} ", 2, &[Sad]),
+    Decoration::new("(^-^*)/ ", 2, &[Happy]),
+    Decoration::new("( ~*-*)~ ", 2, &[Happy]),
+    Decoration::new("(* ^ ω ^) ", 2, &[Happy]),
+    Decoration::new("(´ ∀ ` *) ", 2, &[Happy]),
+    Decoration::new("(o^▽^o) ", 2, &[Happy]),
+    Decoration::new("(⌒▽⌒)☆ ", 2, &[Happy]),
+    Decoration::new("(・∀・)ﾉ ", 2, &[Happy]),
+    Decoration::new("(￣ω￣) ", 2, &[Happy]),
+    Decoration::new("(o･ω･o) ", 2, &[Happy]),
+    Decoration::new("(^人^) ", 2, &[Happy]),
+    Decoration::new("(*´▽`*) ", 2, &[Happy, Shy]),
+    Decoration::new("(≧◡≦) ", 2, &[Happy]),
+    Decoration::new("(o´∀`o) ", 2, &[Happy]),
+    Decoration::new("(＾▽＾) ", 2, &[Happy]),
+    Decoration::new("(⌒ω⌒) ", 2, &[Happy]),
+    Decoration::new("╰(▔∀▔)╯ ", 2, &[Happy]),
+    Decoration::new("(*^‿^*) ", 2, &[Happy, Shy]),
+    Decoration::new("(✯◡✯) ", 2, &[Happy]),
+    Decoration::new("(*≧ω≦*) ", 2, &[Happy]),
+    Decoration::new("(☆▽☆) ", 2, &[Happy]),
+    Decoration::new("＼(≧▽≦)／ ", 2, &[Happy]),
+    Decoration::new("(*°▽°*) ", 2, &[Happy]),
+    Decoration::new("(✧ω✧) ", 2, &[Happy]),
+    Decoration::new("(>∀<☆)ノ ", 2, &[Happy]),
+    Decoration::new("(≧▽≦)o ", 2, &[Happy]),
+    Decoration::new("(☆ω☆) ", 2, &[Happy]),
+    Decoration::new("(っ˘ω˘ς ) ", 2, &[Shy]),
+    Decoration::new("\\(★ω★)/ ", 2, &[Happy]),
+    Decoration::new("(╯✧▽✧)╯ ", 2, &[Happy]),
+    Decoration::new("(>ω<)o ", 2, &[Happy, Shy]),
+    Decoration::new("(´ ω `♡) ", 2, &[Shy]),
+    Decoration::new("(♡°▽°♡) ", 2, &[Happy]),
+    Decoration::new("♡(｡- ω -) ", 2, &[Shy]),
+    Decoration::new("(❤ω❤) ", 2, &[Happy]),
+    Decoration::new("(*ﾉωﾉ) ", 2, &[Shy]),
+    Decoration::new("(＃￣ω￣) ", 1, &[Angry]),
+    Decoration::new("(＞ｍ＜) ", 1, &[Angry]),
+    Decoration::new("(ᗒᗣᗕ)՞ ", 1, &[Angry]),
+    Decoration::new("(＃`Д´) ", 1, &[Angry]),
+    Decoration::new("(°ㅂ°╬) ", 1, &[Angry]),
+    Decoration::new("(╬ Ò﹏Ó) ", 1, &[Angry]),
+    Decoration::new("(´-ω-`) ", 2, &[Sad]),
+    Decoration::new("(-ω-、) ", 2, &[Sad]),
+    Decoration::new("( ╥ω╥ ) ", 2, &[Sad]),
+    Decoration::new("(ノωヽ) ", 2, &[Sad, Shy]),
+    Decoration::new("(・_・ヾ ", 2, &[Shy]),
+    Decoration::new("╮(￣ω￣;)╭ ", 2, &[Shy]),
+    Decoration::new("(*・ω・)ﾉ ", 2, &[Happy]),
+    Decoration::new("(✧∀✧)/ ", 2, &[Happy]),
+    Decoration::new("(つ≧▽≦)つ ", 2, &[Happy]),
+    Decoration::new("(つ✧ω✧)つ ", 2, &[Happy]),
+    Decoration::new("⊂(･ω･*⊂) ", 2, &[Happy, Shy]),
+    Decoration::new("(^ω~) ", 2, &[Happy]),
+    Decoration::new("|･ω･) ", 2, &[Shy]),
+    Decoration::new("(=^･ω･^=) ", 2, &[Cat]),
+    Decoration::new("(=^･ｪ･^=) ", 2, &[Cat]),
+    Decoration::new("(=①ω①=) ", 2, &[Cat]),
+    Decoration::new("( =ω=)..nyaa ", 2, &[Cat]),
+    Decoration::new("(=`ω´=) ", 2, &[Cat, Angry]),
+    Decoration::new("(=^‥^=) ", 2, &[Cat]),
+    Decoration::new("( =ノωヽ=) ", 2, &[Cat, Shy]),
+    Decoration::new("(=^-ω-^=) ", 2, &[Cat]),
+    Decoration::new("(=`ω´=)ノ” ", 2, &[Cat, Angry]),
+    Decoration::new("(/ =ω=)/ ", 2, &[Cat]),
+    Decoration::new("(•ㅅ•❀)ฅ ", 2, &[Cat]),
+    Decoration::new("( =ω= ) ", 2, &[Cat]),
+    Decoration::new("(^◔ᴥ◔^) ", 2, &[Cat]),
+    Decoration::new("(　･ω･)☞ ", 2, &[Happy]),
+];
+
+pub static ACTIONS: [Decoration; 19] = [
+    Decoration::new("*notices bulge* ", 1, &[Nsfw]),
+    Decoration::new("*cries* ", 2, &[Sad]),
+    Decoration::new("*hugs tightly* ", 3, &[Happy]),
+    Decoration::new("*screams* ", 2, &[Angry]),
+    Decoration::new("*looks away* ", 2, &[Shy]),
+    Decoration::new("*blushes* ", 3, &[Shy]),
+    Decoration::new("*sweats* ", 2, &[Shy]),
+    Decoration::new("*cuddles you* ", 3, &[Happy]),
+    Decoration::new("*moans* ", 1, &[Nsfw]),
+    Decoration::new("*giggles shyly* ", 3, &[Shy, Happy]),
+    Decoration::new("*looks at you* ", 2, &[Shy]),
+    Decoration::new("*twerks* ", 1, &[Nsfw]),
+    Decoration::new("*sighs* ", 2, &[Sad]),
+    Decoration::new("*leans over* ", 2, &[Shy]),
+    Decoration::new("*pokes you* ", 2, &[Happy]),
+    Decoration::new("*teleports behind you* ", 1, &[Angry]),
+    Decoration::new("*shuffles closer* ", 2, &[Shy]),
+    Decoration::new("*flushes* ", 2, &[Shy]),
+    Decoration::new("*bites lips* ", 1, &[Nsfw]),
+];
+
+/// Whole words replaced by [`AlgorithmVersion::V3`](crate::AlgorithmVersion::V3), sorted by
+/// the word
+pub const DICTIONARY: [(&str, &str); 14] = [
+    ("cute", "kawaii"),
+    ("hi", "hai"),
+    ("love", "wuv"),
+    ("now", "nyow"),
+    ("small", "smol"),
+    ("stop", "stawp"),
+    ("that", "dat"),
+    ("the", "da"),
+    ("this", "dis"),
+    ("what", "wut"),
+    ("with", "wif"),
+    ("you", "chu"),
+    ("you're", "ur"),
+    ("your", "ur"),
+];
+
+/// Words that tell the mood of a sentence, for
+/// [`UwUConfig::sentiment`](crate::UwUConfig::sentiment), sorted by the word
+pub const MOODS: [(&str, Category); 57] = [
+    ("alone", Sad),
+    ("amazing", Happy),
+    ("angry", Angry),
+    ("annoyed", Angry),
+    ("annoying", Angry),
+    ("awesome", Happy),
+    ("awful", Angry),
+    ("beautiful", Happy),
+    ("best", Happy),
+    ("broken", Sad),
+    ("celebrate", Happy),
+    ("cool", Happy),
+    ("cry", Sad),
+    ("crying", Sad),
+    ("cute", Happy),
+    ("depressed", Sad),
+    ("enjoy", Happy),
+    ("excited", Happy),
+    ("fun", Happy),
+    ("furious", Angry),
+    ("glad", Happy),
+    ("gone", Sad),
+    ("good", Happy),
+    ("great", Happy),
+    ("haha", Happy),
+    ("happy", Happy),
+    ("hate", Angry),
+    ("hooray", Happy),
+    ("hurt", Sad),
+    ("laugh", Happy),
+    ("lonely", Sad),
+    ("lost", Sad),
+    ("love", Happy),
+    ("lovely", Happy),
+    ("mad", Angry),
+    ("miss", Sad),
+    ("nice", Happy),
+    ("pain", Sad),
+    ("perfect", Happy),
+    ("rage", Angry),
+    ("regret", Sad),
+    ("sad", Sad),
+    ("sigh", Sad),
+    ("smile", Happy),
+    ("sorry", Sad),
+    ("stupid", Angry),
+    ("tears", Sad),
+    ("terrible", Angry),
+    ("thanks", Happy),
+    ("tired", Sad),
+    ("ugh", Angry),
+    ("unfortunately", Sad),
+    ("unhappy", Sad),
+    ("upset", Angry),
+    ("wonderful", Happy),
+    ("worst", Angry),
+    ("yay", Happy),
+];
+
+/// Common English words, most common first, for telling which of the letters that UwUifying
+/// turns into W a word had
+pub const WORDS: [&str; 292] = [
+    "will",
+    "one",
+    "all",
+    "would",
+    "there",
+    "their",
+    "what",
+    "who",
+    "which",
+    "when",
+    "were",
+    "your",
+    "people",
+    "now",
+    "no",
+    "other",
+    "only",
+    "new",
+    "over",
+    "know",
+    "year",
+    "like",
+    "well",
+    "also",
+    "really",
+    "way",
+    "work",
+    "world",
+    "first",
+    "look",
+    "more",
+    "after",
+    "where",
+    "right",
+    "very",
+    "our",
+    "could",
+    "long",
+    "little",
+    "here",
+    "life",
+    "own",
+    "while",
+    "down",
+    "never",
+    "under",
+    "last",
+    "place",
+    "why",
+    "great",
+    "old",
+    "still",
+    "call",
+    "different",
+    "around",
+    "large",
+    "help",
+    "line",
+    "another",
+    "small",
+    "war",
+    "read",
+    "light",
+    "play",
+    "near",
+    "learn",
+    "real",
+    "left",
+    "later",
+    "love",
+    "number",
+    "need",
+    "follow",
+    "land",
+    "name",
+    "word",
+    "form",
+    "live",
+    "low",
+    "answer",
+    "cross",
+    "turn",
+    "tell",
+    "sure",
+    "role",
+    "free",
+    "area",
+    "course",
+    "clear",
+    "problem",
+    "story",
+    "north",
+    "across",
+    "early",
+    "important",
+    "along",
+    "power",
+    "door",
+    "car",
+    "close",
+    "walk",
+    "white",
+    "river",
+    "run",
+    "order",
+    "plan",
+    "part",
+    "rule",
+    "red",
+    "feel",
+    "fall",
+    "bring",
+    "girl",
+    "slow",
+    "family",
+    "friend",
+    "grow",
+    "paper",
+    "travel",
+    "several",
+    "animal",
+    "color",
+    "hear",
+    "already",
+    "warm",
+    "morning",
+    "full",
+    "hold",
+    "window",
+    "nothing",
+    "lot",
+    "level",
+    "rest",
+    "whole",
+    "money",
+    "interest",
+    "wrong",
+    "woman",
+    "women",
+    "children",
+    "child",
+    "person",
+    "heart",
+    "listen",
+    "language",
+    "known",
+    "knew",
+    "news",
+    "night",
+    "nine",
+    "anyone",
+    "nice",
+    "none",
+    "note",
+    "notice",
+    "nobody",
+    "nor",
+    "normal",
+    "noon",
+    "nature",
+    "neck",
+    "nose",
+    "hello",
+    "sorry",
+    "please",
+    "lovely",
+    "friends",
+    "cry",
+    "crying",
+    "player",
+    "playing",
+    "truly",
+    "believe",
+    "loved",
+    "lonely",
+    "alone",
+    "ruler",
+    "roll",
+    "rolling",
+    "round",
+    "running",
+    "ready",
+    "road",
+    "room",
+    "rain",
+    "rather",
+    "relax",
+    "remember",
+    "rock",
+    "rose",
+    "brother",
+    "sister",
+    "mother",
+    "father",
+    "daughter",
+    "ever",
+    "water",
+    "letter",
+    "better",
+    "flower",
+    "flowers",
+    "colour",
+    "dollar",
+    "sugar",
+    "from",
+    "for",
+    "or",
+    "are",
+    "before",
+    "program",
+    "pretty",
+    "proud",
+    "probably",
+    "blue",
+    "black",
+    "blow",
+    "clean",
+    "cloud",
+    "glass",
+    "glad",
+    "glow",
+    "plant",
+    "pleasure",
+    "sleep",
+    "smile",
+    "smell",
+    "fresh",
+    "front",
+    "fruit",
+    "dry",
+    "try",
+    "trust",
+    "true",
+    "truth",
+    "tree",
+    "three",
+    "through",
+    "throw",
+    "strong",
+    "street",
+    "straight",
+    "string",
+    "wonderful",
+    "wild",
+    "wind",
+    "winter",
+    "wish",
+    "with",
+    "without",
+    "worry",
+    "worse",
+    "worst",
+    "write",
+    "green",
+    "ground",
+    "group",
+    "break",
+    "bread",
+    "brown",
+    "class",
+    "clock",
+    "climb",
+    "cold",
+    "always",
+    "almost",
+    "although",
+    "evening",
+    "kill",
+    "milk",
+    "hill",
+    "hills",
+    "fill",
+    "tall",
+    "wall",
+    "ball",
+    "cool",
+    "fool",
+    "pool",
+    "school",
+    "tool",
+    "few",
+    "new",
+    "knew",
+    "view",
+    "drew",
+    "grew",
+    "crew",
+    "threw",
+    "flew",
+    "blew",
+    "chew",
+    "stew",
+];
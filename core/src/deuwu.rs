@@ -0,0 +1,406 @@
+use std::fmt::{Error, Write};
+use std::ops::Range;
+
+use crate::constants::WORDS;
+use crate::rules::match_case;
+use crate::uwu::{split_punctuation, unstutter, Seeding, UwUify};
+
+/// Most spellings tried for one word. Words with more letters that could have been something
+/// else only get the best guess
+const MAX_CANDIDATES: usize = 1024;
+
+/// Where a word is in the text with the faces and actions taken out, and the ones that came
+/// before it
+type Stripped<'a> = (Range<usize>, Vec<&'a str>);
+
+/// Writes `text` with faces, actions and stutters taken out and the rules undone, keeping the
+/// whitespace as it is
+pub(crate) fn deuwuify<T: Write>(uwuify: &UwUify, text: &str, out: &mut T) -> Result<(), Error> {
+    let (text, words) = strip_decorations(uwuify, text);
+    // With context seeding the seed of a word depends on the original words around it
+    let exact = uwuify.config().seeding() != Seeding::Context;
+    // Guesses go through every pass, each with the seed it was UwUified with
+    let reseeded: Vec<UwUify> = (1..uwuify.config().passes())
+        .filter_map(|pass| uwuify.reseeded(pass))
+        .collect();
+    let passes: Vec<&UwUify> = std::iter::once(uwuify).chain(&reseeded).collect();
+
+    let mut position = 0;
+    let mut written = 0;
+    for (range, decorations) in words {
+        out.write_str(&text[written..range.start])?;
+        let word = restore(&passes, &text[range.clone()], &decorations, position, exact);
+        position += uwuify.uwuify_bare(&word, position, &mut String::new()).0;
+        out.write_str(&word)?;
+        written = range.end;
+    }
+    out.write_str(&text[written..])
+}
+
+/// Takes out every face and action of the algorithm and the config that stands on its own
+/// between whitespace, along with the space that was written next to it. Returns the text
+/// that's left, and where each word is in it with the faces and actions that came before it
+fn strip_decorations<'a>(uwuify: &'a UwUify, text: &str) -> (String, Vec<Stripped<'a>>) {
+    let mut out = String::with_capacity(text.len());
+    let mut words = Vec::new();
+    let mut before = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let decoration = uwuify.written.iter().find(|decoration| {
+            rest.strip_prefix(decoration.as_str())
+                .is_some_and(|after| after.is_empty() || after.starts_with(char::is_whitespace))
+        });
+
+        if let Some(decoration) = decoration {
+            // Faces before a word are followed by a space, ones after it come after one
+            rest = &rest[decoration.len()..];
+            match rest.strip_prefix(' ') {
+                Some(after) => rest = after,
+                None if out.ends_with(' ') => {
+                    out.pop();
+                }
+                None => {}
+            }
+            before.push(decoration.as_str());
+            continue;
+        }
+
+        let word = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let end = rest[word..]
+            .find(|c: char| !c.is_whitespace())
+            .map_or(rest.len(), |space| word + space);
+        if word > 0 {
+            words.push((out.len()..out.len() + word, std::mem::take(&mut before)));
+        }
+        out.push_str(&rest[..end]);
+        rest = &rest[end..];
+    }
+
+    (out, words)
+}
+
+/// The original of `word`, which had `position` words and then `decorations` before it. When
+/// `exact`, every spelling it could have been is UwUified again with all the `passes`, and the
+/// ones that give back the same word and the same faces and actions in front of it are kept, or
+/// failing that the ones that give back the same word. Out of those, common words win, then
+/// words from the dictionary, then the best guess
+fn restore(
+    passes: &[&UwUify],
+    word: &str,
+    decorations: &[&str],
+    position: u64,
+    exact: bool,
+) -> String {
+    let uwuify = passes[0];
+    if split_punctuation(word, true).1.is_empty() {
+        return word.to_owned();
+    }
+
+    // Older versions stutter whatever the word starts with, punctuation and all, and with more
+    // than one pass they stutter words that already do
+    let separates_punctuation = uwuify.config().algorithm().separates_punctuation();
+    let (leading, core, trailing) = split_punctuation(word, true);
+    let mut stutters = vec![if separates_punctuation { core } else { word }];
+    while let Some(unstuttered) = unstutter(stutters[stutters.len() - 1]) {
+        stutters.push(unstuttered);
+    }
+    let spellings: Vec<_> = stutters
+        .into_iter()
+        .rev()
+        .map(|spelling| {
+            if separates_punctuation {
+                (leading, spelling, trailing)
+            } else {
+                split_punctuation(spelling, true)
+            }
+        })
+        .collect();
+
+    let mut candidates = Vec::new();
+    for (leading, core, trailing) in spellings {
+        let originals = uwuify
+            .dictionary
+            .originals(core)
+            .into_iter()
+            .map(|original| {
+                let mut candidate = String::new();
+                match_case(core, original, &mut candidate);
+                candidate
+            });
+        candidates.extend(
+            originals
+                .chain(respellings(core))
+                .map(|candidate| format!("{leading}{candidate}{trailing}")),
+        );
+    }
+    if exact {
+        // Faces and actions after the word before can only be told apart from the ones in
+        // front of this one when there are none of them
+        let config = uwuify.config();
+        let after_words = config.sentence_faces() > 0.0 || config.sentence_actions() > 0.0;
+
+        let mut out = String::new();
+        let checked: Vec<(&String, bool, bool)> = candidates
+            .iter()
+            .map(|candidate| {
+                let expected = uwuify_passes(passes, candidate, position, &mut out);
+                let expected: Vec<&str> = expected.iter().map(String::as_str).collect();
+                let same_decorations = decorations.ends_with(&expected)
+                    && (after_words || decorations.len() == expected.len());
+                (candidate, out == word, same_decorations)
+            })
+            .collect();
+
+        let same: Vec<String> = checked
+            .iter()
+            .filter(|(_, same_word, same_decorations)| *same_word && *same_decorations)
+            .map(|(candidate, ..)| candidate.to_string())
+            .collect();
+        let same_word: Vec<String> = checked
+            .iter()
+            .filter(|(_, same_word, _)| *same_word)
+            .map(|(candidate, ..)| candidate.to_string())
+            .collect();
+        if !same.is_empty() {
+            candidates = same;
+        } else if !same_word.is_empty() {
+            candidates = same_word;
+        }
+    }
+
+    let rank = |candidate: &str| {
+        let (_, core, _) = split_punctuation(candidate, true);
+        WORDS
+            .iter()
+            .position(|common| common.eq_ignore_ascii_case(core))
+            .unwrap_or(usize::MAX)
+    };
+    // min_by_key keeps the first of equals, which is the one that was tried first
+    candidates
+        .into_iter()
+        .min_by_key(|candidate| rank(candidate))
+        .unwrap()
+}
+
+/// Writes `text` into `out` the way it's UwUified by all the `passes`, with stutters but
+/// without any faces or actions, and returns the faces and actions that go in front of it.
+/// Words that already have them don't get more on the next pass
+fn uwuify_passes(passes: &[&UwUify], text: &str, position: u64, out: &mut String) -> Vec<String> {
+    let mut decorations = Vec::new();
+    let mut text = text.to_owned();
+    for uwuify in passes {
+        out.clear();
+        let (_, added) = uwuify.uwuify_bare(&text, position, out);
+        if decorations.is_empty() {
+            decorations = added.into_iter().map(str::to_owned).collect();
+        }
+        text.clone_from(out);
+    }
+    decorations
+}
+
+/// Every spelling that the rules could have turned into `word`, the best guess first. A W could
+/// have been an L, an R or a W, and a Y between an N and a vowel could have been added
+fn respellings(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let options: Vec<&[&str]> = chars
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| match c {
+            'w' => &["w", "l", "r"][..],
+            'W' => &["W", "L", "R"][..],
+            'y' if i > 0
+                && matches!(chars[i - 1], 'n' | 'N')
+                && chars
+                    .get(i + 1)
+                    .is_some_and(|next| "aeiouAEIOU".contains(*next)) =>
+            {
+                &["", "y"][..]
+            }
+            _ => &[][..],
+        })
+        .collect();
+
+    let count = options.iter().try_fold(1usize, |count, options| {
+        count
+            .checked_mul(options.len().max(1))
+            .filter(|count| *count <= MAX_CANDIDATES)
+    });
+
+    let mut spellings = vec![guess(&chars)];
+    let Some(count) = count else {
+        return spellings;
+    };
+    for mut n in 0..count {
+        let mut spelling = String::with_capacity(word.len());
+        for (c, options) in chars.iter().zip(&options) {
+            if options.is_empty() {
+                spelling.push(*c);
+            } else {
+                spelling.push_str(options[n % options.len()]);
+                n /= options.len();
+            }
+        }
+        if !spellings.contains(&spelling) {
+            spellings.push(spelling);
+        }
+    }
+    spellings
+}
+
+/// The most likely spelling of `word` when there's nothing else to go by: a double W was a
+/// double L, so was a W before a Y at the end, a W after an E at the end was an R, and a Y
+/// between an N and a vowel was added. Any other W is left alone
+fn guess(word: &[char]) -> String {
+    let is_w = |i: usize| word.get(i).is_some_and(|c| matches!(c, 'w' | 'W'));
+    let mut out = String::with_capacity(word.len());
+
+    for (i, &c) in word.iter().enumerate() {
+        match c {
+            'w' | 'W'
+                if (i > 0 && is_w(i - 1))
+                    || is_w(i + 1)
+                    || (i + 2 == word.len() && word[i + 1] == 'y') =>
+            {
+                out.push(if c == 'W' { 'L' } else { 'l' });
+            }
+            'w' | 'W' if i + 1 == word.len() && i > 1 && matches!(word[i - 1], 'e' | 'E') => {
+                out.push(if c == 'W' { 'R' } else { 'r' });
+            }
+            'y' if i > 0
+                && matches!(word[i - 1], 'n' | 'N')
+                && word
+                    .get(i + 1)
+                    .is_some_and(|next| "aeiouAEIOU".contains(*next)) => {}
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{guess, respellings};
+    use crate::algorithm::AlgorithmVersion;
+    use crate::config::UwUConfig;
+    use crate::uwu::{Seeding, UwUify, Whitespace};
+
+    fn deuwuify(uwuify: &UwUify, text: &str) -> String {
+        let mut out = String::new();
+        uwuify.deuwuify(text, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn guesses() {
+        let guess = |word: &str| guess(&word.chars().collect::<Vec<_>>());
+        assert_eq!(guess("hewwo"), "hello");
+        assert_eq!(guess("Weawwy"), "Weally");
+        assert_eq!(guess("onwy"), "only");
+        assert_eq!(guess("nyothing"), "nothing");
+        assert_eq!(guess("NyO"), "NO");
+        assert_eq!(guess("wow"), "wow");
+        assert_eq!(guess("mattew"), "matter");
+
+        assert_eq!(respellings("hewwo").len(), 9);
+        assert_eq!(respellings("nya").len(), 2);
+        assert_eq!(respellings("www").len(), 27);
+        assert_eq!(respellings(&"w".repeat(20)).len(), 1);
+    }
+
+    #[test]
+    fn heuristics() {
+        // Context seeding can't be checked, so only the word list and the guesses are used
+        let uwuify = UwUify::from_config(UwUConfig::builder().seeding(Seeding::Context).build());
+        assert_eq!(
+            deuwuify(
+                &uwuify,
+                "OwO h-hewwo *hugs tightly* wowwd!\n\nI w-weawwy wuv chu ( ╥ω╥ )\n"
+            ),
+            "hello world!\n\nI really love you\n"
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        // None of these are in the word list, and "squiwwew" could be spelled 27 ways. Every
+        // word gets a face and an action, which are different for each spelling
+        let text = "Marvelous squirrels quarrel\n\n\tover walnuts, (never near a meadow).";
+
+        for version in AlgorithmVersion::ALL {
+            for seeding in [Seeding::Word, Seeding::Position] {
+                for seed in 0..10 {
+                    let config = UwUConfig::builder()
+                        .faces(1.0)
+                        .actions(1.0)
+                        .stutters(0.5)
+                        .algorithm(*version)
+                        .seeding(seeding)
+                        .seed(seed)
+                        .whitespace(Whitespace::Preserve)
+                        .build();
+                    let uwuify = UwUify::from_config(config);
+
+                    let mut uwu = String::new();
+                    uwuify.uwuify_sentence(text, &mut uwu).unwrap();
+                    assert_eq!(deuwuify(&uwuify, &uwu), text, "{version:?} {uwu:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn default_round_trips() {
+        // With the default probabilities most words have nothing that tells their spellings
+        // apart, so they don't always come back as they were, but they always UwUify the same
+        let text =
+            "Quarrelled narwhals rarely eat walnuts, (x-rays) and da\n\nQuiet, smug magpies hop.";
+
+        for version in AlgorithmVersion::ALL {
+            for seed in 0..20 {
+                let uwuify = UwUify::from_config(
+                    UwUConfig::builder()
+                        .algorithm(*version)
+                        .seed(seed)
+                        .whitespace(Whitespace::Preserve)
+                        .build(),
+                );
+
+                let mut uwu = String::new();
+                uwuify.uwuify_sentence(text, &mut uwu).unwrap();
+                let plain = deuwuify(&uwuify, &uwu);
+                let mut again = String::new();
+                uwuify.uwuify_sentence(&plain, &mut again).unwrap();
+                assert_eq!(again, uwu, "{version:?} {seed} {plain:?}");
+
+                // Words the rules don't change come back as they were
+                assert!(
+                    plain.ends_with("\n\nQuiet, smug magpies hop."),
+                    "{version:?} {plain:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn passes() {
+        // Before V8 the next pass UwUifies the faces and actions, so they can't be taken out
+        let text = "Hello there, I really love you. Only you!\n";
+
+        for seed in 0..50 {
+            let uwuify = UwUify::from_config(
+                UwUConfig::builder()
+                    .stutters(0.5)
+                    .seed(seed)
+                    .whitespace(Whitespace::Preserve)
+                    .passes(2)
+                    .build(),
+            );
+
+            let mut uwu = String::new();
+            uwuify.uwuify_sentence(text, &mut uwu).unwrap();
+            assert_eq!(deuwuify(&uwuify, &uwu), text, "{seed} {uwu:?}");
+        }
+    }
+}
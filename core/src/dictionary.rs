@@ -0,0 +1,95 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::rules::match_case;
+use crate::uwu::split_punctuation;
+
+/// Whole words that are replaced instead of going through the rules. Words are looked up in
+/// lower case, without the punctuation around them
+#[derive(Debug, Default)]
+pub(crate) struct Dictionary(HashMap<String, String>);
+
+impl Dictionary {
+    /// Custom words replace built-in ones with the same spelling
+    pub(crate) fn new(builtin: &[(&str, &str)], custom: &BTreeMap<String, String>) -> Self {
+        let builtin = builtin
+            .iter()
+            .map(|(word, replacement)| (word.to_string(), replacement.to_string()));
+        let custom = custom
+            .iter()
+            .map(|(word, replacement)| (word.to_lowercase(), replacement.clone()));
+
+        Self(builtin.chain(custom).collect())
+    }
+
+    /// Writes the replacement for `word` into `out` if there is one, in the same case as the
+    /// word and with its punctuation kept. See [`split_punctuation`] for `graphemes`
+    pub(crate) fn replace(&self, word: &str, graphemes: bool, out: &mut String) -> bool {
+        if self.0.is_empty() {
+            return false;
+        }
+
+        let (leading, core, trailing) = split_punctuation(word, graphemes);
+        if core.is_empty() {
+            return false;
+        }
+
+        let Some(replacement) = self.0.get(&core.to_lowercase()) else {
+            return false;
+        };
+
+        out.push_str(leading);
+        match_case(core, replacement, out);
+        out.push_str(trailing);
+        true
+    }
+
+    /// The words that are replaced with `replacement`, in lower case and sorted
+    pub(crate) fn originals(&self, replacement: &str) -> Vec<&str> {
+        let replacement = replacement.to_lowercase();
+        let mut originals: Vec<&str> = self
+            .0
+            .iter()
+            .filter(|(_, other)| other.to_lowercase() == replacement)
+            .map(|(word, _)| word.as_str())
+            .collect();
+        originals.sort_unstable();
+        originals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Dictionary;
+
+    fn replace(dictionary: &Dictionary, word: &str) -> Option<String> {
+        let mut out = String::new();
+        dictionary.replace(word, true, &mut out).then_some(out)
+    }
+
+    #[test]
+    fn whole_words() {
+        let custom = BTreeMap::from([("Senpai".to_owned(), "senpaiii".to_owned())]);
+        let dictionary = Dictionary::new(&[("you", "chu"), ("the", "da")], &custom);
+
+        assert_eq!(replace(&dictionary, "you").as_deref(), Some("chu"));
+        assert_eq!(replace(&dictionary, "You,").as_deref(), Some("Chu,"));
+        assert_eq!(replace(&dictionary, "(THE)").as_deref(), Some("(DA)"));
+        assert_eq!(
+            replace(&dictionary, "senpai!").as_deref(),
+            Some("senpaiii!")
+        );
+        assert_eq!(replace(&dictionary, "young"), None);
+        assert_eq!(replace(&dictionary, "the\u{301}"), None);
+        assert_eq!(replace(&dictionary, "...").as_deref(), None);
+    }
+
+    #[test]
+    fn custom_words_win() {
+        let custom = BTreeMap::from([("you".to_owned(), "u".to_owned())]);
+        let dictionary = Dictionary::new(&[("you", "chu")], &custom);
+
+        assert_eq!(replace(&dictionary, "you").as_deref(), Some("u"));
+    }
+}
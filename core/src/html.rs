@@ -0,0 +1,338 @@
+use std::fmt::{Error, Write};
+
+use crate::uwu::{Escape, Progress, UwUify, Whitespace};
+
+/// Elements whose contents are never markup, and are never shown as text
+const RAW_TEXT: [&str; 2] = ["script", "style"];
+/// Elements whose contents are never markup, but are still shown as text
+const ESCAPABLE_RAW_TEXT: [&str; 2] = ["textarea", "title"];
+/// Elements whose text is shown, but shouldn't be UwUified
+const VERBATIM: [&str; 2] = ["code", "pre"];
+/// Elements that start or end a paragraph, for the limits on faces and actions
+const PARAGRAPHS: [&str; 14] = [
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dt",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "p",
+    "td",
+];
+
+enum Markup<'a> {
+    StartTag { name: &'a str, self_closing: bool },
+    EndTag(&'a str),
+    Other,
+}
+
+/// UwUifies the visible text of an HTML document or snippet, copying tags, attributes,
+/// comments and entities from `html` byte for byte
+pub fn uwuify<T: Write>(uwuify: &UwUify, html: &str, out: &mut T) -> Result<(), Error> {
+    let mut pos = 0;
+    let mut text_start = 0;
+    let mut verbatim = 0usize;
+    let mut progress = Progress::default();
+
+    while let Some(offset) = html[pos..].find('<') {
+        let start = pos + offset;
+        let Some((len, markup)) = parse_markup(&html[start..]) else {
+            pos = start + 1;
+            continue;
+        };
+        let end = start + len;
+
+        write_text(
+            uwuify,
+            &html[text_start..start],
+            verbatim > 0,
+            &mut progress,
+            out,
+        )?;
+        out.write_str(&html[start..end])?;
+        pos = end;
+        text_start = end;
+
+        if let Markup::StartTag { name, .. } | Markup::EndTag(name) = markup {
+            if PARAGRAPHS.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+                progress.end_paragraph();
+            }
+        }
+
+        match markup {
+            Markup::StartTag {
+                name,
+                self_closing: false,
+            } => {
+                let is = |names: &[&str]| names.iter().any(|n| n.eq_ignore_ascii_case(name));
+
+                if is(&RAW_TEXT) || is(&ESCAPABLE_RAW_TEXT) {
+                    let close = find_end_tag(&html[end..], name).map_or(html.len(), |i| end + i);
+                    write_text(
+                        uwuify,
+                        &html[end..close],
+                        verbatim > 0 || is(&RAW_TEXT),
+                        &mut progress,
+                        out,
+                    )?;
+                    pos = close;
+                    text_start = close;
+                } else if is(&VERBATIM) {
+                    verbatim += 1;
+                }
+            }
+            Markup::EndTag(name) if VERBATIM.iter().any(|n| n.eq_ignore_ascii_case(name)) => {
+                verbatim = verbatim.saturating_sub(1)
+            }
+            _ => {}
+        }
+    }
+
+    write_text(
+        uwuify,
+        &html[text_start..],
+        verbatim > 0,
+        &mut progress,
+        out,
+    )
+}
+
+fn write_text<T: Write>(
+    uwuify: &UwUify,
+    text: &str,
+    verbatim: bool,
+    progress: &mut Progress,
+    out: &mut T,
+) -> Result<(), Error> {
+    if verbatim {
+        return out.write_str(text);
+    }
+
+    // Faces and actions that are already there are found before the text is split at entities,
+    // which escaped ones are full of
+    let mut start = 0;
+    for decoration in uwuify.existing_decorations(text, Escape::Html) {
+        write_prose(uwuify, &text[start..decoration.start], progress, out)?;
+        out.write_str(&text[decoration.clone()])?;
+        progress.existing_decoration();
+        start = decoration.end;
+    }
+    write_prose(uwuify, &text[start..], progress, out)
+}
+
+/// UwUifies `text`, copying the character references in it as they are
+fn write_prose<T: Write>(
+    uwuify: &UwUify,
+    text: &str,
+    progress: &mut Progress,
+    out: &mut T,
+) -> Result<(), Error> {
+    let mut start = 0;
+    for (amp, _) in text.match_indices('&') {
+        let len = entity_len(&text[amp..]);
+        if amp < start || len == 0 {
+            continue;
+        }
+
+        uwuify.uwuify_text(
+            &text[start..amp],
+            Whitespace::Preserve,
+            Escape::Html,
+            progress,
+            out,
+        )?;
+        out.write_str(&text[amp..amp + len])?;
+        start = amp + len;
+    }
+
+    uwuify.uwuify_text(
+        &text[start..],
+        Whitespace::Preserve,
+        Escape::Html,
+        progress,
+        out,
+    )
+}
+
+/// Length of the character reference at the start of `text`, or 0 if there isn't one
+fn entity_len(text: &str) -> usize {
+    let name = text[1..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '#'))
+        .unwrap_or(text.len() - 1);
+
+    if name > 0 && text[1 + name..].starts_with(';') {
+        name + 2
+    } else {
+        0
+    }
+}
+
+/// Parses the tag, comment or declaration at the start of `text`, returning its length.
+/// Returns `None` if the `<` is just text
+fn parse_markup(text: &str) -> Option<(usize, Markup<'_>)> {
+    if let Some(comment) = text.strip_prefix("<!--") {
+        let end = comment.find("-->").map_or(text.len(), |end| end + 7);
+        return Some((end, Markup::Other));
+    }
+
+    match *text.as_bytes().get(1)? {
+        b'!' | b'?' => Some((
+            text.find('>').map_or(text.len(), |end| end + 1),
+            Markup::Other,
+        )),
+        b'/' if text.as_bytes().get(2)?.is_ascii_alphabetic() => {
+            Some((tag_len(text), Markup::EndTag(tag_name(&text[2..]))))
+        }
+        c if c.is_ascii_alphabetic() => {
+            let len = tag_len(text);
+            Some((
+                len,
+                Markup::StartTag {
+                    name: tag_name(&text[1..]),
+                    self_closing: text[..len].ends_with("/>"),
+                },
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn tag_name(text: &str) -> &str {
+    let end = text
+        .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+/// Finds the `>` closing the tag at the start of `text`, skipping over quoted attribute values
+fn tag_len(text: &str) -> usize {
+    let mut quote = None;
+    let mut previous = ' ';
+
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => continue,
+            None if (c == '"' || c == '\'') && previous == '=' => quote = Some(c),
+            None if c == '>' => return i + 1,
+            None => {}
+        }
+
+        if !c.is_ascii_whitespace() {
+            previous = c;
+        }
+    }
+
+    text.len()
+}
+
+/// Finds the start of the end tag for the raw text element `name` in `text`
+fn find_end_tag(text: &str, name: &str) -> Option<usize> {
+    text.match_indices("</").map(|(i, _)| i).find(|&i| {
+        let rest = &text.as_bytes()[i + 2..];
+        rest.len() >= name.len()
+            && rest[..name.len()].eq_ignore_ascii_case(name.as_bytes())
+            && rest
+                .get(name.len())
+                .is_none_or(|c| c.is_ascii_whitespace() || matches!(c, b'/' | b'>'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{UwUConfig, UwUConfigBuilder};
+    use crate::packs::Decoration;
+    use crate::uwu::{Escape, InputMode, UwUify};
+
+    fn uwuify(config: UwUConfigBuilder, text: &str) -> String {
+        let uwuify = UwUify::from_config(config.mode(InputMode::Html).build());
+
+        let mut out = String::new();
+        uwuify.uwuify_sentence(text, &mut out).unwrap();
+        out
+    }
+
+    fn words_only() -> UwUConfigBuilder {
+        UwUConfig::builder().faces(0.0).actions(0.0).stutters(0.0)
+    }
+
+    #[test]
+    fn text_nodes_only() {
+        let html = "<!DOCTYPE html>\n<html><head><title>Hello world</title>\
+            <link rel=\"stylesheet\" href='normalize.css'>\
+            <style>p { color: red; }</style></head>\n\
+            <body><p class=\"lead\" title=\"really\">Hello <b>world</b> &amp; others&nbsp;:3</p>\
+            <!-- largely <p>ignored</p> -->\
+            <pre>let r = 1;</pre><p>Run <code>rm -rf <i>later</i></code> later</p>\
+            <script>if (a < b && rel) {}</script></body></html>";
+
+        assert_eq!(
+            uwuify(words_only(), html),
+            "<!DOCTYPE html>\n<html><head><title>Hewwo wowwd</title>\
+            <link rel=\"stylesheet\" href='normalize.css'>\
+            <style>p { color: red; }</style></head>\n\
+            <body><p class=\"lead\" title=\"really\">Hewwo <b>wowwd</b> &amp; othews&nbsp;:3</p>\
+            <!-- largely <p>ignored</p> -->\
+            <pre>let r = 1;</pre><p>Wun <code>rm -rf <i>later</i></code> watew</p>\
+            <script>if (a < b && rel) {}</script></body></html>"
+        );
+    }
+
+    #[test]
+    fn decorations_are_escaped() {
+        let config = words_only().faces(1.0);
+        let out = self::uwuify(config, "<p>hello there little world</p>");
+        let text = out
+            .strip_prefix("<p>")
+            .and_then(|out| out.strip_suffix("</p>"))
+            .unwrap();
+        assert!(!text.contains(['<', '>']), "{out}");
+    }
+
+    #[test]
+    fn passes() {
+        let config = UwUConfig::builder()
+            .faces(0.5)
+            .actions(0.5)
+            .seed(1)
+            .custom_face(Decoration::custom(">w< ", 1));
+        let text = "<p>Hello there, I really love you so much my <b>friend</b>.</p>";
+        let first = uwuify(config.clone(), text);
+        let twice = uwuify(config.clone().passes(2), text);
+
+        // Every face and action from the first pass is still whole after the second
+        let mut kept = 0;
+        for decoration in &UwUify::from_config(config.build()).written {
+            let mut escaped = String::new();
+            Escape::Html.write(decoration, &mut escaped).unwrap();
+            let count = first.matches(&format!(" {escaped} ")).count();
+            assert!(
+                twice.matches(&escaped).count() >= count,
+                "{escaped} in {twice}"
+            );
+            kept += count;
+        }
+        assert!(kept > 3, "{first}");
+        assert!(
+            !twice.contains("&gt;&gt;") && !twice.contains("w-w&lt;"),
+            "{twice}"
+        );
+
+        // Ones that aren't escaped are left alone too
+        assert_eq!(
+            uwuify(
+                words_only()
+                    .stutters(1.0)
+                    .custom_face(Decoration::custom(">w< ", 1)),
+                "<p>&gt;w&lt; hello >w< there</p>"
+            ),
+            "<p>&gt;w&lt; h-hewwo >w< t-thewe</p>"
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use crate::rules::{Anchor, Case, Rule};
+
+/// Which language's rules UwUify words with, since
+/// [`AlgorithmVersion::V6`](crate::AlgorithmVersion::V6)
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Language {
+    /// Picks the language of every word from its script: Cyrillic words are Russian, words
+    /// with kana are Japanese, and everything else is English
+    #[default]
+    Auto,
+    English,
+    Spanish,
+    German,
+    Russian,
+    Japanese,
+}
+
+impl Language {
+    pub const ALL: &'static [Language] = &[
+        Language::Auto,
+        Language::English,
+        Language::Spanish,
+        Language::German,
+        Language::Russian,
+        Language::Japanese,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::Auto => "auto",
+            Language::English => "english",
+            Language::Spanish => "spanish",
+            Language::German => "german",
+            Language::Russian => "russian",
+            Language::Japanese => "japanese",
+        }
+    }
+
+    /// The language `word` is in, going by the first letter in a script with its own rules
+    pub(crate) fn detect(word: &str) -> Language {
+        word.chars()
+            .find_map(|c| match c {
+                '\u{400}'..='\u{52F}' => Some(Language::Russian),
+                '\u{3040}'..='\u{30FF}' | '\u{FF66}'..='\u{FF9F}' => Some(Language::Japanese),
+                _ => None,
+            })
+            .unwrap_or(Language::English)
+    }
+
+    /// The rules of this language in [`AlgorithmVersion::V6`](crate::AlgorithmVersion::V6),
+    /// except English, which uses the rules of the algorithm
+    pub(crate) fn rules_v6(self) -> Vec<Rule> {
+        let pattern = |find, replace| Rule::pattern(find, replace).unwrap();
+        let preserve = |find, replace| Rule::literal(find, replace).with_case(Case::Preserve);
+
+        match self {
+            Language::Auto | Language::English => Vec::new(),
+            Language::Spanish => vec![
+                // "perro" becomes "pewo" and "llama" becomes "yama"
+                preserve("rr", "w"),
+                preserve("ll", "y"),
+                pattern("([Nn])([AEIOUÁÉÍÓÚaeiouáéíóú])", "${1}y$2"),
+                preserve("l", "w"),
+                preserve("r", "w"),
+            ],
+            Language::German => vec![
+                // "Vater" becomes "Vata", like it's said
+                preserve("er", "a").with_anchor(Anchor::End),
+                pattern("([Nn])([AEIOUÄÖÜaeiouäöü])", "${1}y$2"),
+                preserve("l", "w"),
+                preserve("r", "w"),
+            ],
+            Language::Russian => vec![
+                pattern("([Нн])а", "${1}я"),
+                pattern("[рл]", "в"),
+                pattern("[РЛ]", "В"),
+            ],
+            Language::Japanese => vec![
+                // ら行 becomes わ行, and な becomes にゃ
+                Rule::literal("ら", "わ"),
+                Rule::literal("り", "ゐ"),
+                Rule::literal("る", "う"),
+                Rule::literal("れ", "ゑ"),
+                Rule::literal("ろ", "を"),
+                Rule::literal("な", "にゃ"),
+                Rule::literal("ラ", "ワ"),
+                Rule::literal("リ", "ヰ"),
+                Rule::literal("ル", "ウ"),
+                Rule::literal("レ", "ヱ"),
+                Rule::literal("ロ", "ヲ"),
+                Rule::literal("ナ", "ニャ"),
+            ],
+        }
+    }
+}
+
+impl std::str::FromStr for Language {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Language::ALL
+            .iter()
+            .copied()
+            .find(|language| language.name().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Language;
+    use crate::rules;
+
+    fn uwuify(language: Language, word: &str) -> String {
+        let mut out = String::new();
+        rules::apply(
+            &rules::rule_set(&[], language.rules_v6()),
+            word,
+            true,
+            &mut out,
+        );
+        out
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(Language::detect("hello"), Language::English);
+        assert_eq!(Language::detect("«Привет»"), Language::Russian);
+        assert_eq!(Language::detect("日本語はらくだ"), Language::Japanese);
+        assert_eq!(Language::detect("ｶﾗｽ"), Language::Japanese);
+        assert_eq!(Language::detect("日本"), Language::English);
+    }
+
+    #[test]
+    fn packs() {
+        assert_eq!(uwuify(Language::Spanish, "Perro"), "Pewo");
+        assert_eq!(uwuify(Language::Spanish, "LLAMA"), "YAMA");
+        assert_eq!(uwuify(Language::Spanish, "no"), "nyo");
+        assert_eq!(uwuify(Language::German, "Vater"), "Vata");
+        assert_eq!(uwuify(Language::German, "Liebe"), "Wiebe");
+        assert_eq!(uwuify(Language::Russian, "Наверно"), "Нявевно");
+        assert_eq!(uwuify(Language::Russian, "ЛЮБЛЮ"), "ВЮБВЮ");
+        assert_eq!(uwuify(Language::Japanese, "らりるれろ"), "わゐうゑを");
+        assert_eq!(uwuify(Language::Japanese, "なにラーメン"), "にゃにワーメン");
+    }
+
+    #[test]
+    fn names() {
+        for language in Language::ALL {
+            assert_eq!(language.name().parse(), Ok(*language));
+        }
+    }
+}
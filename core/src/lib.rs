@@ -0,0 +1,26 @@
+#![cfg_attr(all(uwu_bench, test), feature(test))]
+
+mod algorithm;
+mod config;
+mod constants;
+mod deuwu;
+mod dictionary;
+mod html;
+mod language;
+#[cfg(feature = "markdown")]
+mod markdown;
+mod packs;
+#[cfg(feature = "serde")]
+mod preset;
+mod rules;
+mod sentiment;
+mod uwu;
+
+pub use algorithm::AlgorithmVersion;
+pub use config::{parse_seed, random_seed, UwUConfig, UwUConfigBuilder, DEFAULT_SEED};
+pub use language::Language;
+pub use packs::{Category, Decoration};
+#[cfg(feature = "serde")]
+pub use preset::{PresetError, PresetFormat, PRESET_VERSION};
+pub use rules::{Anchor, Case, Rule, RuleError, RuleKind};
+pub use uwu::{InputMode, Seeding, Segmentation, Token, UwUIter, UwUify, Whitespace, Word};
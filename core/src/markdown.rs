@@ -0,0 +1,193 @@
+use std::fmt::{Error, Write};
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use crate::uwu::{Escape, Progress, UwUify, Whitespace};
+
+/// UwUifies the prose of a Markdown document, copying everything else (code, link
+/// targets, markup, raw HTML) from `text` byte for byte
+pub fn uwuify<T: Write>(uwuify: &UwUify, text: &str, out: &mut T) -> Result<(), Error> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+
+    let mut written = 0;
+    let mut verbatim = 0usize;
+    let mut progress = Progress::default();
+    // Faces and actions that are already there, the parser would read actions as emphasis
+    let decorations = uwuify.existing_decorations(text, Escape::Markdown);
+    let mut next_decoration = 0;
+
+    for (event, range) in Parser::new_ext(text, options).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_) | Tag::MetadataBlock(_)) => verbatim += 1,
+            Event::End(TagEnd::CodeBlock | TagEnd::MetadataBlock(_)) => {
+                verbatim = verbatim.saturating_sub(1)
+            }
+            Event::End(
+                TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableCell,
+            ) => progress.end_paragraph(),
+            // Escapes and entities are reported as text that differs from the source, those
+            // are left alone so they don't get mangled
+            Event::Text(prose)
+                if verbatim == 0 && range.start >= written && text[range.clone()] == *prose =>
+            {
+                let mut start = range.start;
+                loop {
+                    while decorations
+                        .get(next_decoration)
+                        .is_some_and(|decoration| decoration.end <= start)
+                    {
+                        progress.existing_decoration();
+                        next_decoration += 1;
+                    }
+                    if start >= range.end {
+                        break;
+                    }
+
+                    // Faces and actions are skipped, and copied with the rest of the source
+                    let end = match decorations.get(next_decoration) {
+                        Some(decoration) if decoration.start < range.end => {
+                            decoration.start.max(start)
+                        }
+                        _ => range.end,
+                    };
+                    if start < end {
+                        out.write_str(&text[written..start])?;
+                        uwuify.uwuify_text(
+                            &text[start..end],
+                            Whitespace::Preserve,
+                            Escape::Markdown,
+                            &mut progress,
+                            out,
+                        )?;
+                        written = end;
+                    }
+                    start = match decorations.get(next_decoration) {
+                        Some(decoration) if end < range.end => decoration.end.min(range.end),
+                        _ => range.end,
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    out.write_str(&text[written..])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::UwUConfig;
+    use crate::packs::Decoration;
+    use crate::uwu::{Escape, InputMode, UwUify};
+
+    fn uwuify(text: &str) -> String {
+        let uwuify = UwUify::from_config(
+            UwUConfig::builder()
+                .faces(0.0)
+                .actions(0.0)
+                .stutters(0.0)
+                .mode(InputMode::Markdown)
+                .build(),
+        );
+
+        let mut out = String::new();
+        uwuify.uwuify_sentence(text, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn prose_only() {
+        assert_eq!(
+            uwuify("# Hello *world*\n\nRead the [README](http://example.org/readme) or `run`.\n"),
+            "# Hewwo *wowwd*\n\nWead da [WEADME](http://example.org/readme) ow `run`.\n"
+        );
+    }
+
+    #[test]
+    fn code_blocks() {
+        let text = "Look:\n\n```rust\nlet r = 1;\n```\n\n    for l in list {}\n";
+        assert_eq!(
+            uwuify(text),
+            "Wook:\n\n```rust\nlet r = 1;\n```\n\n    for l in list {}\n"
+        );
+    }
+
+    #[test]
+    fn escapes_and_links() {
+        assert_eq!(
+            uwuify("- [ ] learn \\*rust\\* &lt;3 at www.rust-lang.org\n"),
+            "- [ ] weawn \\*wust\\* &lt;3 at www.rust-lang.org\n"
+        );
+    }
+
+    #[test]
+    fn escaped_decorations() {
+        let uwuify = |face: &str| {
+            let uwuify = UwUify::from_config(
+                UwUConfig::builder()
+                    .faces(1.0)
+                    .actions(0.0)
+                    .stutters(0.0)
+                    .builtin_faces(false)
+                    .custom_face(Decoration::custom(face, 1))
+                    .mode(InputMode::Markdown)
+                    .build(),
+            );
+
+            let mut out = String::new();
+            uwuify.uwuify_sentence("hi\n\nthere", &mut out).unwrap();
+            out
+        };
+
+        // Neither a quote nor emphasis, and the backslash stays
+        assert_eq!(uwuify(">_>"), "\\>\\_\\> hai\n\n\\>\\_\\> thewe");
+        assert_eq!(uwuify("\\(★ω★)/"), "\\\\(★ω★)/ hai\n\n\\\\(★ω★)/ thewe");
+    }
+
+    #[test]
+    fn existing_decorations() {
+        // Actions are read as emphasis, but they're still left alone
+        assert_eq!(
+            uwuify("*hugs tightly* hello OwO \\*hugs tightly\\* there `OwO`\n"),
+            "*hugs tightly* hewwo OwO \\*hugs tightly\\* thewe `OwO`\n"
+        );
+    }
+
+    #[test]
+    fn passes() {
+        let config = UwUConfig::builder()
+            .actions(0.5)
+            .seed(1)
+            .mode(InputMode::Markdown);
+        let uwuify = |config: UwUConfig, text: &str| {
+            let mut out = String::new();
+            UwUify::from_config(config)
+                .uwuify_sentence(text, &mut out)
+                .unwrap();
+            out
+        };
+
+        let text = "Hello there, I really love you so much my *friend*.\n";
+        let first = uwuify(config.clone().build(), text);
+        let twice = uwuify(config.passes(2).build(), text);
+
+        // Every face and action from the first pass is still whole after the second
+        let written = &UwUify::default().written;
+        let mut kept = 0;
+        for decoration in written {
+            let mut escaped = String::new();
+            Escape::Markdown.write(decoration, &mut escaped).unwrap();
+            let count = first.matches(&format!(" {escaped} ")).count();
+            assert!(
+                twice.matches(&escaped).count() >= count,
+                "{escaped} in {twice}"
+            );
+            kept += count;
+        }
+        assert!(kept > 3, "{first}");
+        assert!(twice.ends_with(" fwiend*.\n"), "{twice}");
+    }
+}
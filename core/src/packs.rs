@@ -0,0 +1,296 @@
+use std::borrow::Cow;
+
+use crate::config::UwUConfig;
+use crate::sentiment::MOOD_BIAS;
+
+/// What a face or an action is like, so whole kinds of them can be left out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Category {
+    Happy,
+    Sad,
+    Angry,
+    Cat,
+    Shy,
+    /// Not safe for work, these are never picked in SFW mode
+    Nsfw,
+}
+
+impl Category {
+    pub const ALL: &'static [Category] = &[
+        Category::Happy,
+        Category::Sad,
+        Category::Angry,
+        Category::Cat,
+        Category::Shy,
+        Category::Nsfw,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Happy => "happy",
+            Category::Sad => "sad",
+            Category::Angry => "angry",
+            Category::Cat => "cat",
+            Category::Shy => "shy",
+            Category::Nsfw => "nsfw",
+        }
+    }
+}
+
+impl std::str::FromStr for Category {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Category::ALL
+            .iter()
+            .copied()
+            .find(|category| category.name().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+/// A face or an action, either from the tables of an
+/// [`AlgorithmVersion`](crate::AlgorithmVersion) or a custom one from the config
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Decoration {
+    text: Cow<'static, str>,
+    #[cfg_attr(feature = "serde", serde(default = "default_weight"))]
+    weight: u32,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "<[Category]>::is_empty")
+    )]
+    categories: Cow<'static, [Category]>,
+}
+
+#[cfg(feature = "serde")]
+fn default_weight() -> u32 {
+    1
+}
+
+impl Decoration {
+    pub(crate) const fn new(
+        text: &'static str,
+        weight: u32,
+        categories: &'static [Category],
+    ) -> Self {
+        Self {
+            text: Cow::Borrowed(text),
+            weight,
+            categories: Cow::Borrowed(categories),
+        }
+    }
+
+    /// A face or an action of your own. Without categories it can be picked whichever ones
+    /// are turned on
+    pub fn custom(text: impl Into<String>, weight: u32) -> Self {
+        Self {
+            text: Cow::Owned(text.into()),
+            weight,
+            categories: Cow::Borrowed(&[]),
+        }
+    }
+
+    pub fn with_categories(mut self, categories: Vec<Category>) -> Self {
+        self.categories = Cow::Owned(categories);
+        self
+    }
+
+    /// The text put before a word. The built-in ones end in a space, whitespace at the end is
+    /// replaced by a single space when it's written out
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// How often this is picked compared to the others in its table, since V7
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Whether this can be picked with the categories and SFW mode in `config`. Anything NSFW
+    /// is left out in SFW mode, otherwise one of its categories has to be turned on, unless it
+    /// has none
+    pub fn allowed(&self, config: &UwUConfig) -> bool {
+        if config.sfw() && self.categories.contains(&Category::Nsfw) {
+            return false;
+        }
+        self.categories.is_empty()
+            || self
+                .categories
+                .iter()
+                .any(|category| config.categories().contains(category))
+    }
+}
+
+/// The faces or actions that can be picked: the built-in ones if they're used, then the custom
+/// ones. The built-in ones come first so they keep their indices
+pub(crate) fn decorations(
+    builtin: &[Decoration],
+    use_builtin: bool,
+    custom: &[Decoration],
+) -> Vec<Decoration> {
+    let builtin = if use_builtin { builtin } else { &[] };
+    builtin.iter().chain(custom).cloned().collect()
+}
+
+/// The text of every face and action in `tables` as it's written out, so they can be found in
+/// text that already has them. Longest first, so one that starts with a shorter one is found
+/// whole
+pub(crate) fn written(tables: &[&[Decoration]]) -> Vec<String> {
+    let mut written: Vec<String> = tables
+        .iter()
+        .copied()
+        .flatten()
+        .map(|decoration| decoration.text().trim().to_owned())
+        .filter(|text| !text.is_empty())
+        .collect();
+    written.sort_unstable_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    written.dedup();
+    written
+}
+
+/// Picks one of `table` with `random`, which is called with the total weight of everything
+/// that can be picked and returns a number below it. Without `weighted` everything has a
+/// weight of 1, so with nothing left out this is the same as picking an index at random. With a
+/// `mood`, the ones in its category are [`MOOD_BIAS`] times as likely
+pub(crate) fn pick(
+    table: &[Decoration],
+    config: &UwUConfig,
+    weighted: bool,
+    mood: Option<Category>,
+    random: impl FnOnce(u32) -> u32,
+) -> Option<usize> {
+    let weight = |decoration: &Decoration| {
+        let weight = if !decoration.allowed(config) {
+            0
+        } else if weighted {
+            decoration.weight
+        } else {
+            1
+        };
+        match mood {
+            Some(mood) if decoration.categories.contains(&mood) => weight.saturating_mul(MOOD_BIAS),
+            _ => weight,
+        }
+    };
+
+    let total: u32 = table.iter().map(weight).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut left = random(total);
+    table.iter().position(|decoration| {
+        let weight = weight(decoration);
+        if left < weight {
+            true
+        } else {
+            left -= weight;
+            false
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pick, Category, Decoration};
+    use crate::algorithm::AlgorithmVersion;
+    use crate::config::UwUConfig;
+
+    #[test]
+    fn picks() {
+        let faces = AlgorithmVersion::V1.faces();
+        let config = UwUConfig::default();
+
+        // Unweighted with everything allowed is an index into the whole table
+        for i in 0..faces.len() as u32 {
+            assert_eq!(pick(faces, &config, false, None, |_| i), Some(i as usize));
+        }
+
+        // "OwO " has a weight of 3
+        assert_eq!(pick(faces, &config, true, None, |_| 2), Some(0));
+        assert_eq!(pick(faces, &config, true, None, |_| 3), Some(1));
+    }
+
+    #[test]
+    fn categories() {
+        let actions = AlgorithmVersion::V1.actions();
+        let sfw = UwUConfig::builder().sfw(true).build();
+        let cats = UwUConfig::builder().categories(vec![Category::Cat]).build();
+
+        for i in 0..100 {
+            if let Some(action) = pick(actions, &sfw, true, None, |total| i % total) {
+                assert!(!actions[action].categories().contains(&Category::Nsfw));
+            }
+        }
+        assert_eq!(pick(actions, &cats, true, None, |_| 0), None);
+        assert!(pick(AlgorithmVersion::V1.faces(), &cats, true, None, |_| 0).is_some());
+    }
+
+    #[test]
+    fn custom() {
+        let faces = [
+            Decoration::custom("owo", 0),
+            Decoration::custom("nya~", 2).with_categories(vec![Category::Cat]),
+        ];
+        let config = UwUConfig::builder().categories(vec![Category::Sad]).build();
+
+        assert_eq!(pick(&faces, &config, true, None, |_| 0), None);
+        assert_eq!(pick(&faces, &config, false, None, |_| 0), Some(0));
+        assert_eq!(
+            pick(&faces, &UwUConfig::default(), true, None, |_| 1),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn moods() {
+        let faces = [
+            Decoration::custom("owo", 1),
+            Decoration::custom("qwq", 1).with_categories(vec![Category::Sad]),
+        ];
+        let config = UwUConfig::default();
+        let sad = Some(Category::Sad);
+
+        // "qwq" counts 8 times over in a sad sentence, and only once otherwise
+        let total = |mood| {
+            let mut total = 0;
+            pick(&faces, &config, true, mood, |t| {
+                total = t;
+                0
+            });
+            total
+        };
+        assert_eq!(total(sad), 9);
+        assert_eq!(total(Some(Category::Happy)), 2);
+        assert_eq!(total(None), 2);
+
+        assert_eq!(pick(&faces, &config, true, sad, |_| 0), Some(0));
+        assert_eq!(pick(&faces, &config, true, sad, |_| 1), Some(1));
+        assert_eq!(pick(&faces, &config, false, sad, |_| 8), Some(1));
+    }
+
+    #[test]
+    fn tables() {
+        for version in AlgorithmVersion::ALL {
+            for decoration in version.faces().iter().chain(version.actions()) {
+                assert!(decoration.weight() > 0, "{decoration:?}");
+                assert!(!decoration.categories().is_empty(), "{decoration:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn names() {
+        for category in Category::ALL {
+            assert_eq!(category.name().parse(), Ok(*category));
+        }
+    }
+}
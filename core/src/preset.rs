@@ -0,0 +1,294 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::UwUConfig;
+
+/// Version of the preset format written by this version of UwUify. It goes up whenever a
+/// setting that changes the output is added, so older versions refuse presets they would
+/// silently UwUify differently. Version 2 added the rules, categories, SFW mode, custom faces
+/// and actions, sentiment and passes
+pub const PRESET_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetFormat {
+    Json,
+    Toml,
+}
+
+impl PresetFormat {
+    /// Guesses the format from a file name, anything that isn't `.toml` is JSON
+    pub fn from_file_name(name: &str) -> Self {
+        match name.rsplit_once('.') {
+            Some((_, extension)) if extension.eq_ignore_ascii_case("toml") => PresetFormat::Toml,
+            _ => PresetFormat::Json,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            PresetFormat::Json => "json",
+            PresetFormat::Toml => "toml",
+        }
+    }
+}
+
+/// The key of [`UwUConfig::algorithm`], which is never filled in from other settings
+const ALGORITHM: &str = "algorithm";
+
+#[derive(Serialize)]
+struct PresetRef<'a> {
+    version: u32,
+    #[serde(flatten)]
+    config: &'a UwUConfig,
+}
+
+#[derive(Deserialize)]
+struct Preset {
+    version: u32,
+    #[serde(flatten)]
+    config: UwUConfig,
+}
+
+#[derive(Debug)]
+pub enum PresetError {
+    Json(serde_json::Error),
+    Toml(toml::de::Error),
+    /// The preset was saved by a newer version of UwUify
+    Version(u32),
+}
+
+impl fmt::Display for PresetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresetError::Json(e) => write!(f, "invalid JSON preset: {e}"),
+            PresetError::Toml(e) => write!(f, "invalid TOML preset: {e}"),
+            PresetError::Version(version) => write!(
+                f,
+                "preset version {version} is newer than the supported version {PRESET_VERSION}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PresetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PresetError::Json(e) => Some(e),
+            PresetError::Toml(e) => Some(e),
+            PresetError::Version(_) => None,
+        }
+    }
+}
+
+impl UwUConfig {
+    pub fn to_preset(&self, format: PresetFormat) -> String {
+        let preset = PresetRef {
+            version: PRESET_VERSION,
+            config: self,
+        };
+
+        match format {
+            PresetFormat::Json => serde_json::to_string_pretty(&preset).unwrap(),
+            PresetFormat::Toml => toml::to_string(&preset).unwrap(),
+        }
+    }
+
+    pub fn from_preset(preset: &str, format: PresetFormat) -> Result<Self, PresetError> {
+        let preset: Preset = match format {
+            PresetFormat::Json => serde_json::from_str(preset).map_err(PresetError::Json)?,
+            PresetFormat::Toml => toml::from_str(preset).map_err(PresetError::Toml)?,
+        };
+
+        Self::checked(preset)
+    }
+
+    /// Like [`UwUConfig::from_preset`], but the settings the preset doesn't have are taken
+    /// from `defaults` instead of [`UwUConfig::default`]. The algorithm never is, presets
+    /// without one are loaded as V1 either way so they keep giving the same text
+    pub fn from_preset_over(
+        preset: &str,
+        format: PresetFormat,
+        defaults: &UwUConfig,
+    ) -> Result<Self, PresetError> {
+        let preset: Preset = match format {
+            PresetFormat::Json => {
+                let preset = serde_json::from_str(preset).map_err(PresetError::Json)?;
+                let merged = match (serde_json::to_value(defaults).unwrap(), preset) {
+                    (serde_json::Value::Object(mut merged), serde_json::Value::Object(preset)) => {
+                        merged.remove(ALGORITHM);
+                        merged.extend(preset);
+                        serde_json::Value::Object(merged)
+                    }
+                    (_, preset) => preset,
+                };
+                serde_json::from_value(merged).map_err(PresetError::Json)?
+            }
+            PresetFormat::Toml => {
+                let preset: toml::Table = toml::from_str(preset).map_err(PresetError::Toml)?;
+                let mut merged = toml::Table::try_from(defaults).unwrap();
+                merged.remove(ALGORITHM);
+                merged.extend(preset);
+                toml::Value::Table(merged)
+                    .try_into()
+                    .map_err(PresetError::Toml)?
+            }
+        };
+
+        Self::checked(preset)
+    }
+
+    fn checked(preset: Preset) -> Result<Self, PresetError> {
+        if preset.version > PRESET_VERSION {
+            return Err(PresetError::Version(preset.version));
+        }
+
+        Ok(preset.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PresetError, PresetFormat};
+    use crate::algorithm::AlgorithmVersion;
+    use crate::config::UwUConfig;
+    use crate::packs::{Category, Decoration};
+    use crate::rules::{Anchor, Case, Rule};
+    use crate::uwu::{InputMode, Whitespace};
+
+    fn config() -> UwUConfig {
+        UwUConfig::builder()
+            .words(0.5)
+            .rule(Rule::literal("you", "u").with_anchor(Anchor::Word))
+            .rule(
+                Rule::pattern("th([aeiou])", "d$1")
+                    .unwrap()
+                    .with_case(Case::Preserve)
+                    .with_priority(2),
+            )
+            .faces(0.25)
+            .custom_face(
+                Decoration::custom("(ﾉ◕ヮ◕)ﾉ*:･ﾟ✧", 3).with_categories(vec![Category::Happy]),
+            )
+            .custom_action(Decoration::custom("*pats you*", 1))
+            .builtin_actions(false)
+            .seed(u64::MAX)
+            .whitespace(Whitespace::Preserve)
+            .mode(InputMode::Html)
+            .build()
+    }
+
+    #[test]
+    fn round_trip() {
+        for format in [PresetFormat::Json, PresetFormat::Toml] {
+            let preset = config().to_preset(format);
+            assert_eq!(
+                UwUConfig::from_preset(&preset, format).unwrap(),
+                config(),
+                "{preset}"
+            );
+        }
+    }
+
+    #[test]
+    fn custom_decorations() {
+        let preset = "version = 1\n[[custom_faces]]\ntext = \"owo7\"\n";
+        let config = UwUConfig::from_preset(preset, PresetFormat::Toml).unwrap();
+
+        assert_eq!(config.custom_faces(), [Decoration::custom("owo7", 1)]);
+        assert!(config.builtin_faces());
+    }
+
+    #[test]
+    fn toml_preset() {
+        let preset = "version = 1\nfaces = 2\nseed = 420\nwhitespace = \"preserve\"\n";
+        let config = UwUConfig::from_preset(preset, PresetFormat::Toml).unwrap();
+
+        assert_eq!(
+            config,
+            UwUConfig::builder()
+                .faces(1.0)
+                .seed(420)
+                .algorithm(AlgorithmVersion::V1)
+                .whitespace(Whitespace::Preserve)
+                .build()
+        );
+    }
+
+    #[test]
+    fn rules() {
+        let preset = "version = 1\nbuiltin_rules = false\n\n[[rules]]\nfind = \"(\"\nreplace = \"\"\nkind = \"pattern\"\n";
+        let error = UwUConfig::from_preset(preset, PresetFormat::Toml).unwrap_err();
+        assert!(error.to_string().contains("invalid pattern"), "{error}");
+
+        let preset =
+            "version = 1\nbuiltin_rules = false\n\n[[rules]]\nfind = \"r\"\nreplace = \"w\"\n";
+        let config = UwUConfig::from_preset(preset, PresetFormat::Toml).unwrap();
+        assert!(!config.builtin_rules());
+        assert_eq!(config.rules(), [Rule::literal("r", "w")]);
+    }
+
+    #[test]
+    fn versions() {
+        assert!(matches!(
+            UwUConfig::from_preset("{\"version\": 3}", PresetFormat::Json),
+            Err(PresetError::Version(3))
+        ));
+        assert!(UwUConfig::from_preset("{\"version\": 1}", PresetFormat::Json).is_ok());
+        assert!(UwUConfig::default()
+            .to_preset(PresetFormat::Toml)
+            .starts_with("version = 2\n"));
+        assert!(matches!(
+            UwUConfig::from_preset("{\"words\": 0.5}", PresetFormat::Json),
+            Err(PresetError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn defaults() {
+        let defaults = UwUConfig::builder()
+            .faces(0.5)
+            .whitespace(Whitespace::Preserve)
+            .build();
+
+        for (preset, format) in [
+            ("version = 1\nwords = 0.25\n", PresetFormat::Toml),
+            ("{\"version\": 1, \"words\": 0.25}", PresetFormat::Json),
+        ] {
+            let config = UwUConfig::from_preset_over(preset, format, &defaults).unwrap();
+            assert_eq!(
+                config,
+                defaults
+                    .to_builder()
+                    .words(0.25)
+                    .algorithm(AlgorithmVersion::V1)
+                    .build(),
+                "{preset}"
+            );
+        }
+
+        // Without an algorithm it's still V1, the same as through `from_preset`
+        let preset = "version = 1\nseed = 69\n";
+        let v8 = UwUConfig::builder().algorithm(AlgorithmVersion::V8).build();
+        let over = UwUConfig::from_preset_over(preset, PresetFormat::Toml, &v8).unwrap();
+        let loaded = UwUConfig::from_preset(preset, PresetFormat::Toml).unwrap();
+        assert_eq!(over, loaded);
+        assert_eq!(over.algorithm(), AlgorithmVersion::V1);
+
+        let preset = "version = 1\nwhitespace = \"collapse\"\n";
+        let config = UwUConfig::from_preset_over(preset, PresetFormat::Toml, &defaults).unwrap();
+        assert_eq!(config.whitespace(), Whitespace::Collapse);
+
+        assert!(matches!(
+            UwUConfig::from_preset_over("{\"words\": 0.5}", PresetFormat::Json, &defaults),
+            Err(PresetError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(PresetFormat::from_file_name("uwu.TOML"), PresetFormat::Toml);
+        assert_eq!(PresetFormat::from_file_name("uwu.json"), PresetFormat::Json);
+        assert_eq!(PresetFormat::from_file_name("uwu"), PresetFormat::Json);
+    }
+}
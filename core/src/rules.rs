@@ -0,0 +1,388 @@
+use std::fmt;
+
+use regex_lite::{Regex, RegexBuilder};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// How the text a [`Rule`] looks for is written
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum RuleKind {
+    /// Exactly this text
+    #[default]
+    Literal,
+    /// A regular expression, the replacement can use its groups as `$1` or `${name}`
+    Pattern,
+}
+
+/// Where in a word a [`Rule`] is allowed to match. The edges of a word are wherever a letter or
+/// digit is next to something that isn't one, so punctuation doesn't get in the way
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Anchor {
+    #[default]
+    Anywhere,
+    /// Only at the start of a word
+    Start,
+    /// Only at the end of a word
+    End,
+    /// Only the whole word
+    Word,
+}
+
+/// How a [`Rule`] treats upper and lower case
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Case {
+    /// Only matches text in exactly the same case
+    #[default]
+    Sensitive,
+    /// Matches text in any case, and writes the replacement as it is
+    Insensitive,
+    /// Matches text in any case, and writes the replacement in the case of the text it
+    /// replaces: all caps, capitalized or as it is
+    Preserve,
+}
+
+impl Case {
+    pub const ALL: &'static [Case] = &[Case::Sensitive, Case::Insensitive, Case::Preserve];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Case::Sensitive => "sensitive",
+            Case::Insensitive => "insensitive",
+            Case::Preserve => "preserve",
+        }
+    }
+}
+
+impl Anchor {
+    pub const ALL: &'static [Anchor] =
+        &[Anchor::Anywhere, Anchor::Start, Anchor::End, Anchor::Word];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Anchor::Anywhere => "anywhere",
+            Anchor::Start => "start",
+            Anchor::End => "end",
+            Anchor::Word => "word",
+        }
+    }
+}
+
+/// A pattern that is invalid
+#[derive(Debug)]
+pub struct RuleError(regex_lite::Error);
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid pattern: {}", self.0)
+    }
+}
+
+impl std::error::Error for RuleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct RuleDef {
+    find: String,
+    replace: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    kind: RuleKind,
+    #[cfg_attr(feature = "serde", serde(default))]
+    anchor: Anchor,
+    #[cfg_attr(feature = "serde", serde(default))]
+    case: Case,
+    #[cfg_attr(feature = "serde", serde(default))]
+    priority: i32,
+}
+
+/// Replaces some text in a word with something else. Words are scanned from left to right, at
+/// every character the rules are tried from the highest priority to the lowest, and the first
+/// one that matches there is written out and skipped over. Text that no rule matches is kept
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RuleDef", into = "RuleDef"))]
+pub struct Rule {
+    def: RuleDef,
+    regex: Regex,
+}
+
+impl PartialEq for Rule {
+    fn eq(&self, other: &Self) -> bool {
+        self.def == other.def
+    }
+}
+
+impl TryFrom<RuleDef> for Rule {
+    type Error = RuleError;
+
+    fn try_from(def: RuleDef) -> Result<Self, Self::Error> {
+        let pattern = match def.kind {
+            RuleKind::Literal => regex_lite::escape(&def.find),
+            RuleKind::Pattern => def.find.clone(),
+        };
+        let regex = RegexBuilder::new(&format!("^(?:{pattern})"))
+            .case_insensitive(def.case != Case::Sensitive)
+            .build()
+            .map_err(RuleError)?;
+
+        Ok(Self { def, regex })
+    }
+}
+
+impl From<Rule> for RuleDef {
+    fn from(rule: Rule) -> Self {
+        rule.def
+    }
+}
+
+impl Rule {
+    fn new(kind: RuleKind, find: String, replace: String) -> Result<Self, RuleError> {
+        Self::try_from(RuleDef {
+            find,
+            replace,
+            kind,
+            anchor: Anchor::default(),
+            case: Case::default(),
+            priority: 0,
+        })
+    }
+
+    /// Replaces `find` with `replace`, exactly as they're written
+    pub fn literal(find: impl Into<String>, replace: impl Into<String>) -> Self {
+        Self::new(RuleKind::Literal, find.into(), replace.into())
+            .expect("escaped text is always a valid pattern")
+    }
+
+    /// Replaces whatever the regular expression `find` matches with `replace`. Case
+    /// insensitive matching only folds ASCII letters
+    pub fn pattern(find: impl Into<String>, replace: impl Into<String>) -> Result<Self, RuleError> {
+        Self::new(RuleKind::Pattern, find.into(), replace.into())
+    }
+
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.def.anchor = anchor;
+        self
+    }
+
+    pub fn with_case(mut self, case: Case) -> Self {
+        self.def.case = case;
+        Self::try_from(self.def).expect("the case doesn't change whether a pattern is valid")
+    }
+
+    /// Rules with a higher priority are tried first. Rules with the same priority are tried in
+    /// the order they're given, before the built-in ones
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.def.priority = priority;
+        self
+    }
+
+    pub fn find(&self) -> &str {
+        &self.def.find
+    }
+
+    pub fn replace(&self) -> &str {
+        &self.def.replace
+    }
+
+    pub fn kind(&self) -> RuleKind {
+        self.def.kind
+    }
+
+    pub fn anchor(&self) -> Anchor {
+        self.def.anchor
+    }
+
+    pub fn case(&self) -> Case {
+        self.def.case
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.def.priority
+    }
+
+    /// Writes the replacement if this rule matches `word` at `pos`, returning how long the
+    /// text it replaced was
+    fn apply_at(&self, word: &str, pos: usize, out: &mut String) -> Option<usize> {
+        let rest = &word[pos..];
+        let captures = self.regex.captures(rest)?;
+        let matched = captures.get(0)?.as_str();
+        if matched.is_empty() {
+            return None;
+        }
+
+        let is_edge = |c: Option<char>| c.is_none_or(|c| !c.is_alphanumeric());
+        let starts_word = is_edge(word[..pos].chars().next_back());
+        let ends_word = is_edge(rest[matched.len()..].chars().next());
+        let anchored = match self.def.anchor {
+            Anchor::Anywhere => true,
+            Anchor::Start => starts_word,
+            Anchor::End => ends_word,
+            Anchor::Word => starts_word && ends_word,
+        };
+        if !anchored {
+            return None;
+        }
+
+        let start = out.len();
+        match self.def.kind {
+            RuleKind::Literal => out.push_str(&self.def.replace),
+            RuleKind::Pattern => captures.expand(&self.def.replace, out),
+        }
+
+        if self.def.case == Case::Preserve {
+            let replaced = out.split_off(start);
+            match_case(matched, &replaced, out);
+        }
+
+        Some(matched.len())
+    }
+}
+
+/// Writes `text` in the case of `like`: all caps if it's all caps, capitalized if it starts with
+/// a capital letter, and as it is otherwise
+pub(crate) fn match_case(like: &str, text: &str, out: &mut String) {
+    let is_caps = like.chars().any(char::is_uppercase) && !like.chars().any(char::is_lowercase);
+    let is_capitalized = like.chars().next().is_some_and(char::is_uppercase);
+
+    if is_caps {
+        out.extend(text.chars().flat_map(char::to_uppercase));
+    } else if is_capitalized {
+        let mut chars = text.chars();
+        out.extend(chars.next().into_iter().flat_map(char::to_uppercase));
+        out.push_str(chars.as_str());
+    } else {
+        out.push_str(text);
+    }
+}
+
+/// Puts `custom` and the `builtin` rules into the order they're tried in
+pub(crate) fn rule_set(custom: &[Rule], builtin: Vec<Rule>) -> Vec<Rule> {
+    let mut rules: Vec<_> = custom.iter().cloned().chain(builtin).collect();
+    rules.sort_by_key(|rule| std::cmp::Reverse(rule.def.priority));
+    rules
+}
+
+/// Writes `word` into `out` with `rules` applied, they must be in the order from [`rule_set`].
+/// With `graphemes`, rules are only tried at the start of a grapheme cluster, and the rest of a
+/// cluster a replacement ends in is kept, so accents and emoji sequences are never split
+pub(crate) fn apply(rules: &[Rule], word: &str, graphemes: bool, out: &mut String) {
+    let boundaries: Vec<usize> = if graphemes {
+        word.grapheme_indices(true).map(|(i, _)| i).collect()
+    } else {
+        word.char_indices().map(|(i, _)| i).collect()
+    };
+    let next_boundary = |pos: usize| {
+        let i = boundaries.partition_point(|&boundary| boundary < pos);
+        boundaries.get(i).copied().unwrap_or(word.len())
+    };
+    let mut pos = 0;
+
+    'scan: while pos < word.len() {
+        for rule in rules {
+            if let Some(len) = rule.apply_at(word, pos, out) {
+                let end = next_boundary(pos + len);
+                out.push_str(&word[pos + len..end]);
+                pos = end;
+                continue 'scan;
+            }
+        }
+
+        let end = next_boundary(pos + 1);
+        out.push_str(&word[pos..end]);
+        pos = end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, rule_set, Anchor, Case, Rule};
+
+    fn apply_rules(rules: &[Rule], word: &str) -> String {
+        let mut out = String::new();
+        apply(&rule_set(rules, Vec::new()), word, true, &mut out);
+        out
+    }
+
+    #[test]
+    fn literals_and_patterns() {
+        let rules = [
+            Rule::literal("ove", "uv"),
+            Rule::pattern("th([aeiou])", "d$1").unwrap(),
+        ];
+        assert_eq!(apply_rules(&rules, "love"), "luv");
+        assert_eq!(apply_rules(&rules, "the"), "de");
+        assert_eq!(apply_rules(&rules, "Love"), "Luv");
+        assert!(Rule::pattern("(", "").is_err());
+    }
+
+    #[test]
+    fn anchors() {
+        let rules = [Rule::literal("you", "u").with_anchor(Anchor::Word)];
+        assert_eq!(apply_rules(&rules, "you,"), "u,");
+        assert_eq!(apply_rules(&rules, "young"), "young");
+
+        let rules = [Rule::literal("ing", "in'").with_anchor(Anchor::End)];
+        assert_eq!(apply_rules(&rules, "rolling!"), "rollin'!");
+        assert_eq!(apply_rules(&rules, "ingot"), "ingot");
+
+        let rules = [Rule::literal("s", "sh").with_anchor(Anchor::Start)];
+        assert_eq!(apply_rules(&rules, "\"sus\""), "\"shus\"");
+    }
+
+    #[test]
+    fn cases() {
+        let rules = [Rule::literal("the", "da").with_case(Case::Preserve)];
+        assert_eq!(apply_rules(&rules, "the"), "da");
+        assert_eq!(apply_rules(&rules, "The"), "Da");
+        assert_eq!(apply_rules(&rules, "THE"), "DA");
+
+        let rules = [Rule::literal("the", "da").with_case(Case::Insensitive)];
+        assert_eq!(apply_rules(&rules, "THE"), "da");
+
+        let rules = [Rule::literal("the", "da")];
+        assert_eq!(apply_rules(&rules, "THE"), "THE");
+    }
+
+    #[test]
+    fn graphemes() {
+        // "é" as an e and a combining accent
+        let rules = [Rule::literal("e", "i"), Rule::literal("\u{301}", "!")];
+        assert_eq!(apply_rules(&rules, "cafe\u{301}"), "cafi\u{301}");
+
+        let mut out = String::new();
+        apply(
+            &rule_set(&rules, Vec::new()),
+            "cafe\u{301}",
+            false,
+            &mut out,
+        );
+        assert_eq!(out, "cafi!");
+    }
+
+    #[test]
+    fn priorities() {
+        let rules = [
+            Rule::literal("l", "w"),
+            Rule::literal("ll", "w").with_priority(1),
+        ];
+        assert_eq!(apply_rules(&rules, "hello"), "hewo");
+
+        let builtin = vec![Rule::literal("l", "w")];
+        let mut out = String::new();
+        apply(
+            &rule_set(&[Rule::literal("l", "y")], builtin),
+            "lol",
+            true,
+            &mut out,
+        );
+        assert_eq!(out, "yoy");
+    }
+}
@@ -0,0 +1,65 @@
+use crate::constants::MOODS;
+use crate::packs::Category;
+use crate::uwu::split_punctuation;
+
+/// How much more likely faces and actions in the category of the mood are picked
+pub(crate) const MOOD_BIAS: u32 = 8;
+
+/// The mood of a sentence, by which of happy, sad and angry it has the most words for. `None`
+/// when it has none of them, or as many for two moods
+pub(crate) fn mood<'a>(words: impl IntoIterator<Item = &'a str>) -> Option<Category> {
+    let mut scores = [
+        (Category::Happy, 0),
+        (Category::Sad, 0),
+        (Category::Angry, 0),
+    ];
+
+    for word in words {
+        let (_, core, _) = split_punctuation(word, true);
+        let core = core.to_lowercase();
+        if let Ok(i) = MOODS.binary_search_by_key(&core.as_str(), |(word, _)| word) {
+            let (_, score) = scores
+                .iter_mut()
+                .find(|(category, _)| *category == MOODS[i].1)
+                .unwrap();
+            *score += 1;
+        }
+    }
+
+    scores.sort_by(|(_, a), (_, b)| b.cmp(a));
+    match scores {
+        [(_, 0), ..] => None,
+        [(_, first), (_, second), _] if first == second => None,
+        [(mood, _), ..] => Some(mood),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mood;
+    use crate::constants::MOODS;
+    use crate::packs::Category;
+
+    #[test]
+    fn moods() {
+        assert_eq!(
+            mood("I love this, it's great!".split(' ')),
+            Some(Category::Happy)
+        );
+        assert_eq!(
+            mood("Sadly I MISS you, sorry.".split(' ')),
+            Some(Category::Sad)
+        );
+        assert_eq!(
+            mood("ugh, I hate Mondays".split(' ')),
+            Some(Category::Angry)
+        );
+        assert_eq!(mood("The weather is fine".split(' ')), None);
+        assert_eq!(mood("Good, but I'm tired".split(' ')), None);
+    }
+
+    #[test]
+    fn sorted() {
+        assert!(MOODS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
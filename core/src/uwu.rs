@@ -0,0 +1,1825 @@
+use std::borrow::Cow;
+use std::fmt::{Error, Write};
+use std::ops::Range;
+
+use linkify::{LinkFinder, LinkKind};
+use unicode_segmentation::{UWordBounds, UnicodeSegmentation};
+
+use crate::algorithm::AlgorithmVersion;
+use crate::config::{random_seed, UwUConfig};
+use crate::deuwu;
+use crate::dictionary::Dictionary;
+use crate::html;
+use crate::language::Language;
+#[cfg(feature = "markdown")]
+use crate::markdown;
+use crate::packs::{self, Category, Decoration};
+use crate::rules::{self, Rule};
+use crate::sentiment;
+
+/// Every word gets its own random number generator, seeded with the SipHash-2-4 of `$bytes`
+/// one after the other, keyed with `(seed, 0)`. Both of those are fully specified, so the same
+/// seed gives the same text on every platform and with every version of the dependencies
+macro_rules! new_seeder {
+    ($seed:expr, $($bytes:expr),+) => {{
+        let mut hasher = siphasher::sip::SipHasher24::new_with_keys($seed, 0);
+        $(std::hash::Hasher::write(&mut hasher, $bytes);)+
+        <rand_xoshiro::Xoshiro256Plus as rand::SeedableRng>::seed_from_u64(
+            std::hash::Hasher::finish(&hasher),
+        )
+    }};
+}
+
+macro_rules! random_float {
+    ($seeder:expr) => {
+        rand::Rng::gen_range($seeder, 0.0..1.0)
+    };
+}
+
+/// Draws the random numbers that decide what happens to a word
+struct Draws {
+    seeder: rand_xoshiro::Xoshiro256Plus,
+    /// The single number every effect is compared against, when draws aren't independent
+    shared: Option<f64>,
+    independent: bool,
+}
+
+impl Draws {
+    fn new(seeder: rand_xoshiro::Xoshiro256Plus, algorithm: AlgorithmVersion) -> Self {
+        Self {
+            seeder,
+            shared: None,
+            independent: algorithm.independent_draws(),
+        }
+    }
+
+    /// Whether something with a probability of `p` happens
+    fn chance(&mut self, p: f64) -> bool {
+        let value = if self.independent {
+            random_float!(&mut self.seeder)
+        } else {
+            *self
+                .shared
+                .get_or_insert_with(|| random_float!(&mut self.seeder))
+        };
+
+        value <= p
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct Word<'a> {
+    stutter: bool,
+    word: String,
+    /// Length of the punctuation at the start of `word`, the stutter goes after it
+    leading: usize,
+    face: Option<&'a str>,
+    action: Option<&'a str>,
+    /// Face put after the word, when it ends a clause or a sentence
+    end_face: Option<&'a str>,
+    /// Action put after the word, when it ends a sentence and another one follows
+    end_action: Option<&'a str>,
+}
+
+impl<'a> Word<'a> {
+    /// The UwUified word with the punctuation around it, without its stutter
+    pub fn text(&self) -> &str {
+        &self.word
+    }
+
+    /// Whether the first letter of the word is repeated in front of it, after any punctuation
+    pub fn stutter(&self) -> bool {
+        self.stutter
+    }
+
+    /// The face put in front of the word
+    pub fn face(&self) -> Option<&'a str> {
+        self.face
+    }
+
+    /// The action put in front of the word, after the face
+    pub fn action(&self) -> Option<&'a str> {
+        self.action
+    }
+
+    /// The face put after the word, when it ends a clause or a sentence
+    pub fn end_face(&self) -> Option<&'a str> {
+        self.end_face
+    }
+
+    /// The action put after the word, when it ends a sentence and another one follows
+    pub fn end_action(&self) -> Option<&'a str> {
+        self.end_action
+    }
+}
+
+#[derive(Debug)]
+pub enum Token<'a> {
+    Word(Word<'a>),
+    /// Whitespace between two words, exactly as it should be written out
+    Space(&'a str),
+    /// A face or an action that was already in the text, written out as it is
+    Decoration(&'a str),
+}
+
+/// How the whitespace between words is tokenized
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Whitespace {
+    /// Every run of whitespace becomes a single space, and one is added after the last word
+    #[default]
+    Collapse,
+    /// Spaces, tabs and newlines are kept exactly as they appear in the input
+    Preserve,
+}
+
+/// What goes into the seed of each word, on top of the seed in the config
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Seeding {
+    /// Only the word itself, so the same word always gets the same face, action and stutter
+    #[default]
+    Word,
+    /// The word and how many words came before it in the text
+    Position,
+    /// The word and the words right before and after it
+    Context,
+}
+
+impl Seeding {
+    pub const ALL: &'static [Seeding] = &[Seeding::Word, Seeding::Position, Seeding::Context];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Seeding::Word => "word",
+            Seeding::Position => "position",
+            Seeding::Context => "context",
+        }
+    }
+}
+
+impl std::str::FromStr for Seeding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Seeding::ALL
+            .iter()
+            .copied()
+            .find(|seeding| seeding.name().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+/// How text is split into words, on top of the whitespace between them
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Segmentation {
+    /// A word is everything between two runs of whitespace
+    #[default]
+    Spaces,
+    /// Chinese and Japanese, which are written without spaces, are also split at the Unicode
+    /// word boundaries (UAX #29) inside them. Those put every Han character on its own and
+    /// keep runs of katakana together. Thai and other scripts that need a dictionary to find
+    /// their words are still split at spaces only
+    Unicode,
+}
+
+impl Segmentation {
+    pub const ALL: &'static [Segmentation] = &[Segmentation::Spaces, Segmentation::Unicode];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Segmentation::Spaces => "spaces",
+            Segmentation::Unicode => "unicode",
+        }
+    }
+}
+
+impl std::str::FromStr for Segmentation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Segmentation::ALL
+            .iter()
+            .copied()
+            .find(|segmentation| segmentation.name().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+/// Whether `c` is in a script that's written without spaces between words, and that Unicode
+/// word boundaries can split
+fn unspaced(c: char) -> bool {
+    matches!(c,
+        '\u{2E80}'..='\u{2FDF}' // CJK radicals
+        | '\u{3005}'..='\u{3007}' // 々, 〆 and 〇
+        | '\u{3040}'..='\u{30FF}' // Hiragana and katakana
+        | '\u{31F0}'..='\u{31FF}' // Small katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK unified ideographs
+        | '\u{F900}'..='\u{FAFF}' // CJK compatibility ideographs
+        | '\u{FF66}'..='\u{FF9F}' // Halfwidth katakana
+        | '\u{20000}'..='\u{3134F}' // CJK extensions B to G
+    )
+}
+
+/// How faces and actions are written into the output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Escape {
+    Nothing,
+    Html,
+    /// Backslashes in front of the punctuation Markdown would read as markup
+    #[cfg(feature = "markdown")]
+    Markdown,
+}
+
+impl Escape {
+    pub(crate) fn write<T: Write>(self, decoration: &str, out: &mut T) -> Result<(), Error> {
+        match self {
+            Escape::Nothing => out.write_str(decoration),
+            Escape::Html => decoration.chars().try_for_each(|c| match c {
+                '<' => out.write_str("&lt;"),
+                '>' => out.write_str("&gt;"),
+                '&' => out.write_str("&amp;"),
+                c => out.write_char(c),
+            }),
+            #[cfg(feature = "markdown")]
+            Escape::Markdown => decoration.chars().try_for_each(|c| {
+                if matches!(
+                    c,
+                    '\\' | '`' | '*' | '_' | '~' | '[' | ']' | '<' | '>' | '#' | '|' | '&' | '!'
+                ) {
+                    out.write_char('\\')?;
+                }
+                out.write_char(c)
+            }),
+        }
+    }
+}
+
+/// What kind of text is being UwUified
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum InputMode {
+    #[default]
+    Plain,
+    /// Only prose is changed, code, link targets and markup are kept as they are
+    #[cfg(feature = "markdown")]
+    Markdown,
+    /// Only visible text is changed, tags, attributes, entities and code are kept as they are
+    Html,
+}
+
+impl InputMode {
+    pub const ALL: &'static [InputMode] = &[
+        InputMode::Plain,
+        #[cfg(feature = "markdown")]
+        InputMode::Markdown,
+        InputMode::Html,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InputMode::Plain => "Plain text",
+            #[cfg(feature = "markdown")]
+            InputMode::Markdown => "Markdown",
+            InputMode::Html => "HTML",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UwUify {
+    config: UwUConfig,
+    /// The rules words are UwUified with, in the order they're tried. `None` when only the
+    /// built-in rules are used, those have a faster way of applying them
+    rules: Option<Vec<Rule>>,
+    /// The rules for words in other languages than English
+    language_rules: Vec<(Language, Vec<Rule>)>,
+    pub(crate) dictionary: Dictionary,
+    /// The faces and actions that can be picked, built-in and custom
+    pub(crate) faces: Vec<Decoration>,
+    pub(crate) actions: Vec<Decoration>,
+    /// Every face and action as it's written out, see [`packs::written`]
+    pub(crate) written: Vec<String>,
+    linkify: LinkFinder,
+}
+
+impl Default for UwUify {
+    fn default() -> Self {
+        Self::from_config(UwUConfig::default())
+    }
+}
+
+/// Text split at whitespace
+#[derive(Debug, Clone)]
+enum Spaces<'a> {
+    Collapse {
+        rest: &'a str,
+        /// Whether a space goes before the next word, and if it ends a paragraph
+        space: Option<bool>,
+    },
+    Preserve(&'a str),
+}
+
+enum Segment<'a> {
+    Word(&'a str),
+    /// Whitespace as it's written out, and whether it ends a paragraph
+    Space(&'a str, bool),
+    /// A face or an action that's already there
+    Decoration(&'a str),
+}
+
+impl<'a> Spaces<'a> {
+    fn new(text: &'a str, whitespace: Whitespace) -> Self {
+        match whitespace {
+            Whitespace::Collapse => Spaces::Collapse {
+                rest: text,
+                space: None,
+            },
+            Whitespace::Preserve => Spaces::Preserve(text),
+        }
+    }
+}
+
+impl<'a> Spaces<'a> {
+    /// Takes the next word off if it's one of `decorations`, standing on its own between
+    /// whitespace. Faces and actions can have spaces in them, so this has to come before the
+    /// text is split into words
+    fn decoration(&mut self, decorations: &[String]) -> Option<&'a str> {
+        let rest = match self {
+            Spaces::Collapse { rest, space: None } => rest.trim_start(),
+            Spaces::Collapse { .. } => return None,
+            Spaces::Preserve(rest) => rest,
+        };
+        let decoration = decorations.iter().find(|decoration| {
+            rest.strip_prefix(decoration.as_str())
+                .is_some_and(|after| after.is_empty() || after.starts_with(char::is_whitespace))
+        })?;
+        let (decoration, tail) = rest.split_at(decoration.len());
+
+        match self {
+            Spaces::Collapse { rest, space } => {
+                *rest = tail.trim_start();
+                *space = Some(ends_paragraph(&tail[..tail.len() - rest.len()]));
+            }
+            Spaces::Preserve(rest) => *rest = tail,
+        }
+        Some(decoration)
+    }
+}
+
+/// Whether `space` has a blank line in it, or a Unicode paragraph separator
+fn ends_paragraph(space: &str) -> bool {
+    space.matches('\n').nth(1).is_some() || space.contains('\u{2029}')
+}
+
+impl<'a> Iterator for Spaces<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Spaces::Collapse { rest, space } => {
+                if let Some(paragraph) = space.take() {
+                    return Some(Segment::Space(" ", paragraph));
+                }
+
+                let word = rest.trim_start();
+                let end = word.find(char::is_whitespace).unwrap_or(word.len());
+                if end == 0 {
+                    return None;
+                }
+                let (word, tail) = word.split_at(end);
+                *rest = tail.trim_start();
+                *space = Some(ends_paragraph(&tail[..tail.len() - rest.len()]));
+                Some(Segment::Word(word))
+            }
+            Spaces::Preserve(rest) => {
+                let first = rest.chars().next()?;
+                let is_space = first.is_whitespace();
+                let end = rest
+                    .find(|c: char| c.is_whitespace() != is_space)
+                    .unwrap_or(rest.len());
+                let (segment, tail) = rest.split_at(end);
+                *rest = tail;
+
+                Some(if is_space {
+                    Segment::Space(segment, ends_paragraph(segment))
+                } else {
+                    Segment::Word(segment)
+                })
+            }
+        }
+    }
+}
+
+/// Text split into words and the whitespace between them
+#[derive(Debug, Clone)]
+struct Segments<'a> {
+    spaces: Spaces<'a>,
+    segmentation: Segmentation,
+    linkify: &'a LinkFinder,
+    /// Faces and actions that are left alone when they're already in the text
+    decorations: &'a [String],
+    /// What's left of a word that's being split at Unicode word boundaries
+    bounds: Option<UWordBounds<'a>>,
+}
+
+impl<'a> Segments<'a> {
+    fn new(
+        text: &'a str,
+        whitespace: Whitespace,
+        segmentation: Segmentation,
+        linkify: &'a LinkFinder,
+        decorations: &'a [String],
+    ) -> Self {
+        Self {
+            spaces: Spaces::new(text, whitespace),
+            segmentation,
+            linkify,
+            decorations,
+            bounds: None,
+        }
+    }
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(word) = self.bounds.as_mut().and_then(Iterator::next) {
+            return Some(Segment::Word(word));
+        }
+        self.bounds = None;
+
+        if let Some(decoration) = self.spaces.decoration(self.decorations) {
+            return Some(Segment::Decoration(decoration));
+        }
+
+        let word = match self.spaces.next()? {
+            Segment::Word(word) => word,
+            space => return Some(space),
+        };
+
+        // Links are kept in one piece, so they're still left alone
+        if self.segmentation == Segmentation::Unicode
+            && word.contains(unspaced)
+            && self.linkify.links(word).next().is_none()
+        {
+            let mut bounds = word.split_word_bounds();
+            let first = bounds.next();
+            self.bounds = Some(bounds);
+            return first.map(Segment::Word);
+        }
+
+        Some(Segment::Word(word))
+    }
+}
+
+/// How far into a document UwUifying has got, carried over from one run of text to the next
+#[derive(Debug, Default)]
+pub(crate) struct Progress {
+    /// How many words came before the next one
+    position: u64,
+    /// Words since the last one with a face or an action, `None` before the first one
+    since_decorated: Option<u32>,
+    /// How many words in a row have had faces or actions
+    burst: u32,
+    /// Faces and actions so far in the current sentence and paragraph
+    in_sentence: u32,
+    in_paragraph: u32,
+    /// Faces and actions that were already in the text since the last word
+    decorated: u32,
+}
+
+impl Progress {
+    /// Counts a face or an action that was already in the text, outside of what's UwUified
+    pub(crate) fn existing_decoration(&mut self) {
+        self.decorated += 1;
+    }
+
+    pub(crate) fn end_paragraph(&mut self) {
+        self.in_sentence = 0;
+        self.in_paragraph = 0;
+    }
+
+    /// Takes the faces and actions off `token` that would go over the limits in `config`. This
+    /// happens after everything is drawn, so the limits never change anything else. `existing`
+    /// faces and actions were already in the text, they're kept but still count
+    fn limit(
+        &mut self,
+        config: &UwUConfig,
+        token: &mut Word<'_>,
+        boundary: Option<Boundary>,
+        existing: u32,
+    ) {
+        let continues_burst = self.since_decorated == Some(0) && self.burst < config.burst();
+        let rested = self
+            .since_decorated
+            .is_none_or(|since| since >= config.cooldown());
+        let left =
+            |limit: Option<u32>, used: u32| limit.map_or(u32::MAX, |l| l.saturating_sub(used));
+        let mut allowed = if continues_burst || rested {
+            left(config.max_per_sentence(), self.in_sentence)
+                .min(left(config.max_per_paragraph(), self.in_paragraph))
+        } else {
+            0
+        }
+        .saturating_sub(existing);
+
+        let mut kept = existing;
+        for decoration in [
+            &mut token.face,
+            &mut token.action,
+            &mut token.end_face,
+            &mut token.end_action,
+        ] {
+            if decoration.is_some() {
+                if allowed == 0 {
+                    *decoration = None;
+                } else {
+                    allowed -= 1;
+                    kept += 1;
+                }
+            }
+        }
+
+        if kept > 0 {
+            self.burst = if continues_burst { self.burst + 1 } else { 1 };
+            self.since_decorated = Some(0);
+            self.in_sentence += kept;
+            self.in_paragraph += kept;
+        } else {
+            self.burst = 0;
+            self.since_decorated = self.since_decorated.map(|since| since.saturating_add(1));
+        }
+
+        if boundary == Some(Boundary::Sentence) {
+            self.in_sentence = 0;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UwUIter<'a> {
+    segments: Segments<'a>,
+    uwuify: &'a UwUify,
+    progress: Progress,
+    previous: &'a str,
+    /// The mood of the current sentence with [`UwUConfig::sentiment`], `None` until it's scored
+    mood: Option<Option<Category>>,
+}
+
+impl<'a> UwUIter<'a> {
+    fn new(text: &'a str, whitespace: Whitespace, uwuify: &'a UwUify, progress: Progress) -> Self {
+        Self {
+            segments: Segments::new(
+                text,
+                whitespace,
+                uwuify.config.segmentation(),
+                &uwuify.linkify,
+                if uwuify.config.algorithm().keeps_decorations() {
+                    &uwuify.written
+                } else {
+                    &[]
+                },
+            ),
+            uwuify,
+            progress,
+            previous: "",
+            mood: None,
+        }
+    }
+
+    fn seeder(&self, word: &str) -> rand_xoshiro::Xoshiro256Plus {
+        let seed = self.uwuify.config.seed();
+        let position = self.progress.position.to_le_bytes();
+
+        // 0xFF never appears in UTF-8, so it keeps the words apart
+        match self.uwuify.config.seeding() {
+            Seeding::Word => new_seeder!(seed, word.as_bytes()),
+            Seeding::Position => new_seeder!(seed, word.as_bytes(), &[0xFF], &position),
+            Seeding::Context => {
+                let next = self
+                    .segments
+                    .clone()
+                    .find_map(|segment| match segment {
+                        Segment::Word(word) => Some(word),
+                        Segment::Space(..) | Segment::Decoration(_) => None,
+                    })
+                    .unwrap_or("");
+
+                new_seeder!(
+                    seed,
+                    word.as_bytes(),
+                    &[0xFF],
+                    self.previous.as_bytes(),
+                    &[0xFF],
+                    next.as_bytes()
+                )
+            }
+        }
+    }
+
+    /// Picks a face or an action out of `table`, `None` when all of them are left out. This
+    /// samples a `u32` rather than a `usize`, so 32-bit targets like wasm pick the same ones as
+    /// 64-bit targets
+    fn pick(
+        &self,
+        table: &'a [Decoration],
+        seeder: &mut rand_xoshiro::Xoshiro256Plus,
+    ) -> Option<&'a str> {
+        let config = &self.uwuify.config;
+        let mood = self.mood.flatten();
+        packs::pick(
+            table,
+            config,
+            config.algorithm().weighted(),
+            mood,
+            |total| rand::Rng::gen_range(seeder, 0..total),
+        )
+        .map(|picked| table[picked].text().trim_end())
+    }
+
+    /// The mood of the sentence that starts with `word`, going by its words up to the end of
+    /// the sentence or the paragraph
+    fn sentence_mood(&self, word: &'a str) -> Option<Category> {
+        let mut segments = self.segments.clone();
+        let mut next = Some(word);
+        sentiment::mood(std::iter::from_fn(move || {
+            let word = next.take()?;
+            if Boundary::after(word) != Some(Boundary::Sentence) {
+                next = loop {
+                    match segments.next() {
+                        Some(Segment::Word(word)) => break Some(word),
+                        Some(Segment::Space(_, false) | Segment::Decoration(_)) => {}
+                        Some(Segment::Space(_, true)) | None => break None,
+                    }
+                };
+            }
+            Some(word)
+        }))
+    }
+
+    /// Puts a face after `word` if it ends a clause or a sentence, and an action if it ends a
+    /// sentence with more text after it. These are drawn after everything else about the word,
+    /// so they never change what happens to the word itself
+    fn end_sentence(&self, boundary: Option<Boundary>, draws: &mut Draws, token: &mut Word<'a>) {
+        let Some(boundary) = boundary else {
+            return;
+        };
+        let config = &self.uwuify.config;
+
+        if random_float!(&mut draws.seeder) <= config.sentence_faces() {
+            token.end_face = self.pick(&self.uwuify.faces, &mut draws.seeder);
+        }
+
+        let between = boundary == Boundary::Sentence
+            && self
+                .segments
+                .clone()
+                .any(|segment| matches!(segment, Segment::Word(_)));
+        if between && random_float!(&mut draws.seeder) <= config.sentence_actions() {
+            token.end_action = self.pick(&self.uwuify.actions, &mut draws.seeder);
+        }
+    }
+}
+
+/// Where a clause or a sentence ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Boundary {
+    Clause,
+    Sentence,
+}
+
+impl Boundary {
+    /// What `word` ends, going by the punctuation after its last letter or digit. That catches
+    /// `word.`, `word?!"`, a lone `...` and `。` on its own, but also abbreviations like `e.g.`
+    fn after(word: &str) -> Option<Boundary> {
+        let end = word
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_alphanumeric())
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let punctuation = &word[end..];
+
+        if punctuation.contains([
+            '.', '!', '?', '…', '‼', '⁇', '⁈', '⁉', '。', '！', '？', '｡',
+        ]) {
+            Some(Boundary::Sentence)
+        } else if punctuation.contains([',', ';', ':', '、', '，', '；', '：', '､']) {
+            Some(Boundary::Clause)
+        } else {
+            None
+        }
+    }
+}
+
+/// `word` without the stutter at its start, if it has one
+pub(crate) fn unstutter(word: &str) -> Option<&str> {
+    let first = word.graphemes(true).next()?;
+    let rest = word[first.len()..].strip_prefix('-')?;
+    rest.starts_with(first).then_some(rest)
+}
+
+/// Splits `word` into the punctuation before it, the word itself and the punctuation after it.
+/// The word is everything from the first letter or digit to the last one, with `graphemes` the
+/// accents and other marks on the last one are part of the word too
+pub(crate) fn split_punctuation(word: &str, graphemes: bool) -> (&str, &str, &str) {
+    fn split<'a>(
+        word: &'a str,
+        units: impl DoubleEndedIterator<Item = (usize, &'a str)>,
+    ) -> (&'a str, &'a str, &'a str) {
+        let mut letters = units.filter(|(_, unit)| unit.starts_with(char::is_alphanumeric));
+        let Some((start, first)) = letters.next() else {
+            return (word, "", "");
+        };
+        let end = letters
+            .next_back()
+            .map_or(start + first.len(), |(i, unit)| i + unit.len());
+
+        (&word[..start], &word[start..end], &word[end..])
+    }
+
+    if graphemes {
+        split(word, word.grapheme_indices(true))
+    } else {
+        split(
+            word,
+            word.char_indices()
+                .map(|(i, c)| (i, &word[i..i + c.len_utf8()])),
+        )
+    }
+}
+
+impl<'a> Iterator for UwUIter<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let word = match self.segments.next()? {
+            Segment::Word(word) => word,
+            Segment::Space(space, paragraph) => {
+                if paragraph {
+                    self.progress.end_paragraph();
+                    self.mood = None;
+                }
+                return Some(Token::Space(space));
+            }
+            Segment::Decoration(decoration) => {
+                self.progress.existing_decoration();
+                return Some(Token::Decoration(decoration));
+            }
+        };
+
+        let boundary = Boundary::after(word);
+        if self.uwuify.config.sentiment() && self.mood.is_none() {
+            self.mood = Some(self.sentence_mood(word));
+        }
+        let mut token = self.word(word, boundary);
+
+        // A word that already has faces or actions in front of it doesn't get more, and neither
+        // does the end of one that has them after it
+        let decorated = std::mem::take(&mut self.progress.decorated);
+        if decorated > 0 {
+            token.face = None;
+            token.action = None;
+        }
+        let mut after = self
+            .segments
+            .clone()
+            .skip_while(|segment| matches!(segment, Segment::Space(_, false)));
+        if matches!(after.next(), Some(Segment::Decoration(_))) {
+            token.end_face = None;
+            token.end_action = None;
+        }
+
+        self.progress
+            .limit(&self.uwuify.config, &mut token, boundary, decorated);
+        if boundary == Some(Boundary::Sentence) {
+            self.mood = None;
+        }
+        Some(Token::Word(token))
+    }
+}
+
+impl<'a> UwUIter<'a> {
+    fn word(&mut self, word: &'a str, boundary: Option<Boundary>) -> Word<'a> {
+        let config = &self.uwuify.config;
+        let words = config.words();
+        let faces = config.faces();
+        let actions = config.actions();
+        let stutters = config.stutters();
+        let algorithm = config.algorithm();
+
+        let mut draws = Draws::new(self.seeder(word), algorithm);
+        self.progress.position += 1;
+        self.previous = word;
+        let mut token = Word::default();
+
+        let (leading, core, trailing) = if algorithm.separates_punctuation() {
+            split_punctuation(word, algorithm.graphemes())
+        } else {
+            ("", word, "")
+        };
+
+        // Punctuation on its own isn't a word, so nothing happens to it
+        if core.is_empty() {
+            token.word = word.to_owned();
+            self.end_sentence(boundary, &mut draws, &mut token);
+            return token;
+        }
+
+        if draws.chance(faces) {
+            token.face = self.pick(&self.uwuify.faces, &mut draws.seeder);
+        }
+
+        if draws.chance(actions) {
+            token.action = self.pick(&self.uwuify.actions, &mut draws.seeder);
+        }
+
+        // Words that already stutter keep the one they have
+        let stuttered = algorithm.keeps_decorations() && unstutter(core).is_some();
+        token.stutter = draws.chance(stutters) && !stuttered;
+
+        token.leading = leading.len();
+        if self.uwuify.linkify.links(word).count() > 0 || !draws.chance(words) {
+            token.word = word.to_owned();
+        } else {
+            token.word.push_str(leading);
+            if !self
+                .uwuify
+                .dictionary
+                .replace(core, algorithm.graphemes(), &mut token.word)
+            {
+                self.uwuify.uwuify_word(core, &mut token.word);
+            }
+            token.word.push_str(trailing);
+        }
+        self.end_sentence(boundary, &mut draws, &mut token);
+        token
+    }
+}
+
+impl UwUify {
+    pub fn from_config(config: UwUConfig) -> Self {
+        Self {
+            rules: Self::rule_set(&config),
+            language_rules: Self::language_rules(&config),
+            dictionary: Self::dictionary(&config),
+            faces: packs::decorations(
+                config.algorithm().faces(),
+                config.builtin_faces(),
+                config.custom_faces(),
+            ),
+            actions: packs::decorations(
+                config.algorithm().actions(),
+                config.builtin_actions(),
+                config.custom_actions(),
+            ),
+            written: Self::written(&config),
+            config,
+            linkify: {
+                let mut linkify = LinkFinder::new();
+                linkify.kinds(&[LinkKind::Email, LinkKind::Url]);
+                linkify.url_must_have_scheme(false);
+                linkify
+            },
+        }
+    }
+
+    pub fn config(&self) -> &UwUConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: UwUConfig) {
+        self.rules = Self::rule_set(&config);
+        self.language_rules = Self::language_rules(&config);
+        self.dictionary = Self::dictionary(&config);
+        self.faces = packs::decorations(
+            config.algorithm().faces(),
+            config.builtin_faces(),
+            config.custom_faces(),
+        );
+        self.actions = packs::decorations(
+            config.algorithm().actions(),
+            config.builtin_actions(),
+            config.custom_actions(),
+        );
+        self.written = Self::written(&config);
+        self.config = config;
+    }
+
+    /// Writes `word` into `out` with the rules applied
+    fn uwuify_word(&self, word: &str, out: &mut String) {
+        let algorithm = self.config.algorithm();
+        let language = match self.config.language() {
+            Language::Auto if algorithm.language_packs() => Language::detect(word),
+            language => language,
+        };
+        let rules = self
+            .language_rules
+            .iter()
+            .find(|(other, _)| *other == language)
+            .map(|(_, rules)| rules)
+            .or(self.rules.as_ref());
+
+        match rules {
+            Some(rules) => rules::apply(rules, word, self.config.algorithm().graphemes(), out),
+            None => self.config.algorithm().uwuify_word(word, out),
+        }
+    }
+
+    fn written(config: &UwUConfig) -> Vec<String> {
+        let algorithm = config.algorithm();
+        packs::written(&[
+            algorithm.faces(),
+            algorithm.actions(),
+            config.custom_faces(),
+            config.custom_actions(),
+        ])
+    }
+
+    fn dictionary(config: &UwUConfig) -> Dictionary {
+        let builtin = if config.builtin_dictionary() {
+            config.algorithm().dictionary()
+        } else {
+            &[]
+        };
+        Dictionary::new(builtin, config.dictionary())
+    }
+
+    fn language_rules(config: &UwUConfig) -> Vec<(Language, Vec<Rule>)> {
+        let algorithm = config.algorithm();
+        if !algorithm.language_packs() || !config.builtin_rules() {
+            return Vec::new();
+        }
+
+        let language = config.language();
+        let languages = match language {
+            Language::Auto => &[Language::Russian, Language::Japanese][..],
+            Language::English => &[],
+            _ => std::slice::from_ref(&language),
+        };
+        languages
+            .iter()
+            .map(|&language| {
+                let builtin = algorithm.language_rules(language);
+                (language, rules::rule_set(config.rules(), builtin))
+            })
+            .collect()
+    }
+
+    fn rule_set(config: &UwUConfig) -> Option<Vec<Rule>> {
+        if config.builtin_rules() && config.rules().is_empty() {
+            return None;
+        }
+
+        let builtin = if config.builtin_rules() {
+            config.algorithm().rules()
+        } else {
+            Vec::new()
+        };
+        Some(rules::rule_set(config.rules(), builtin))
+    }
+
+    pub fn new_seed(&mut self) {
+        self.set_seed(random_seed());
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.config = self.config.to_builder().seed(seed).build();
+    }
+
+    pub fn uwuify_iter<'a>(&'a self, text: &'a str) -> UwUIter<'a> {
+        UwUIter::new(text, self.config.whitespace(), self, Progress::default())
+    }
+
+    /// Turns UwUified text back into plain text: faces and actions are taken out, stutters are
+    /// undone, and W and Ny go back to the letters they were, going by a list of common words.
+    /// Every guess is UwUified again with this config and seed, and the ones that don't give
+    /// back the same stutters, faces and actions are ruled out. Words that could have been
+    /// spelled several ways and have nothing to tell them apart can come back wrong, but what
+    /// comes back is still picked so it UwUifies the same, unless the seeding is
+    /// [`Seeding::Context`].
+    /// The text is always read as plain text, and only the English rules and the dictionary
+    /// are undone
+    pub fn deuwuify<T: Write>(&self, text: &str, out: &mut T) -> Result<(), Error> {
+        deuwu::deuwuify(self, text, out)
+    }
+
+    pub fn uwuify_sentence<T: Write>(&self, text: &str, out: &mut T) -> Result<(), Error> {
+        let passes = self.config.passes().max(1);
+        let mut text = Cow::Borrowed(text);
+
+        for pass in 0..passes {
+            let reseeded = self.reseeded(pass);
+            let uwuify = reseeded.as_ref().unwrap_or(self);
+
+            if pass + 1 == passes {
+                return uwuify.uwuify_pass(&text, out);
+            }
+            let mut next = String::new();
+            uwuify.uwuify_pass(&text, &mut next)?;
+            text = Cow::Owned(next);
+        }
+        Ok(())
+    }
+
+    /// Every pass after the first gets its own seed, so it doesn't draw the same as the one
+    /// before it
+    pub(crate) fn reseeded(&self, pass: u32) -> Option<UwUify> {
+        (pass > 0).then(|| {
+            let seed = self.config.seed().wrapping_add(u64::from(pass));
+            UwUify::from_config(self.config.to_builder().seed(seed).build())
+        })
+    }
+
+    /// UwUifies `text` once, whatever the number of passes in the config
+    fn uwuify_pass<T: Write>(&self, text: &str, out: &mut T) -> Result<(), Error> {
+        match self.config.mode() {
+            InputMode::Plain => {
+                self.write_tokens(&mut self.uwuify_iter(text), Escape::Nothing, out)
+            }
+            #[cfg(feature = "markdown")]
+            InputMode::Markdown => markdown::uwuify(self, text, out),
+            InputMode::Html => html::uwuify(self, text, out),
+        }
+    }
+
+    /// Where the faces and actions that are already in `text` are, written as they are or with
+    /// `escape`, each standing on its own between whitespace. Markup has to be left out of
+    /// what's UwUified before they're found, so documents look for them in their source.
+    /// Versions before they were left alone have none
+    pub(crate) fn existing_decorations(&self, text: &str, escape: Escape) -> Vec<Range<usize>> {
+        if !self.config.algorithm().keeps_decorations() {
+            return Vec::new();
+        }
+
+        let mut forms: Vec<String> = Vec::with_capacity(self.written.len() * 2);
+        for decoration in &self.written {
+            let mut escaped = String::new();
+            escape.write(decoration, &mut escaped).unwrap();
+            forms.push(decoration.clone());
+            if escaped != *decoration {
+                forms.push(escaped);
+            }
+        }
+        forms.sort_unstable_by_key(|form| std::cmp::Reverse(form.len()));
+
+        let mut decorations = Vec::new();
+        let mut start = 0;
+        while start < text.len() {
+            let rest = &text[start..];
+            let found = forms.iter().find(|form| {
+                rest.strip_prefix(form.as_str())
+                    .is_some_and(|after| after.is_empty() || after.starts_with(char::is_whitespace))
+            });
+
+            let end = match found {
+                Some(form) => {
+                    decorations.push(start..start + form.len());
+                    start + form.len()
+                }
+                None => start + rest.find(char::is_whitespace).unwrap_or(rest.len()),
+            };
+            start = end
+                + text[end..]
+                    .find(|c: char| !c.is_whitespace())
+                    .unwrap_or(text.len() - end);
+        }
+        decorations
+    }
+
+    /// UwUifies one run of text out of a bigger document. `progress` is where the document is
+    /// up to before it, and is moved past the words in `text`
+    pub(crate) fn uwuify_text<T: Write>(
+        &self,
+        text: &str,
+        whitespace: Whitespace,
+        escape: Escape,
+        progress: &mut Progress,
+        out: &mut T,
+    ) -> Result<(), Error> {
+        let mut tokens = UwUIter::new(text, whitespace, self, std::mem::take(progress));
+        self.write_tokens(&mut tokens, escape, out)?;
+        *progress = tokens.progress;
+        Ok(())
+    }
+
+    fn write_tokens<T: Write>(
+        &self,
+        tokens: &mut UwUIter,
+        escape: Escape,
+        out: &mut T,
+    ) -> Result<(), Error> {
+        tokens.try_for_each(|token| match token {
+            Token::Word(word) => self.write_word(&word, escape, out),
+            Token::Space(space) | Token::Decoration(space) => out.write_str(space),
+        })
+    }
+
+    /// Writes `word` with its faces, actions and stutter
+    fn write_word<T: Write>(
+        &self,
+        word: &Word<'_>,
+        escape: Escape,
+        out: &mut T,
+    ) -> Result<(), Error> {
+        let algorithm = self.config.algorithm();
+
+        if let Some(face) = word.face {
+            escape.write(face, out)?;
+            out.write_char(' ')?;
+        }
+
+        if let Some(action) = word.action {
+            escape.write(action, out)?;
+            out.write_char(' ')?;
+        }
+
+        let (leading, rest) = word.word.split_at(word.leading);
+        out.write_str(leading)?;
+
+        if word.stutter {
+            if algorithm.graphemes() {
+                out.write_str(rest.graphemes(true).next().unwrap_or("W"))?;
+                out.write_char('-')?;
+            } else {
+                out.write_fmt(format_args!("{}-", rest.chars().next().unwrap_or('W')))?;
+            }
+        }
+
+        out.write_str(rest)?;
+
+        if let Some(face) = word.end_face {
+            out.write_char(' ')?;
+            escape.write(face, out)?;
+        }
+
+        if let Some(action) = word.end_action {
+            out.write_char(' ')?;
+            escape.write(action, out)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `text` the way it's UwUified when `position` words came before it, with stutters
+    /// but without any faces or actions. Returns how many words it has, and the faces and
+    /// actions that go in front of the first one. De-UwUifying uses this to check its guesses
+    pub(crate) fn uwuify_bare<'a>(
+        &'a self,
+        text: &'a str,
+        position: u64,
+        out: &mut String,
+    ) -> (u64, Vec<&'a str>) {
+        let progress = Progress {
+            position,
+            ..Progress::default()
+        };
+        let mut tokens = UwUIter::new(text, Whitespace::Preserve, self, progress);
+        let mut decorations = None;
+        for token in tokens.by_ref() {
+            match token {
+                Token::Word(word) => {
+                    decorations.get_or_insert_with(|| {
+                        let face = word.face.map(str::trim_start);
+                        let action = word.action.map(str::trim_start);
+                        face.into_iter().chain(action).collect()
+                    });
+                    let word = Word {
+                        face: None,
+                        action: None,
+                        end_face: None,
+                        end_action: None,
+                        ..word
+                    };
+                    self.write_word(&word, Escape::Nothing, out).unwrap();
+                }
+                Token::Space(space) | Token::Decoration(space) => out.push_str(space),
+            }
+        }
+        (
+            tokens.progress.position - position,
+            decorations.unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(uwu_bench)]
+    extern crate test;
+
+    use super::{InputMode, Seeding, Segmentation, Token, UwUify, Whitespace};
+    use crate::algorithm::AlgorithmVersion;
+    use crate::config::{UwUConfig, UwUConfigBuilder};
+    use crate::language::Language;
+    use crate::packs::{Category, Decoration};
+    use crate::rules::{Case, Rule};
+
+    const TEXT: &str = "Hello there,\n\n\tthis is a  poem\nabout rolling hills.\n";
+    const GOLDEN: &str = "Hello there! I really love rolling down the hills near my house, \
+        no matter the weather.";
+
+    fn uwuify(uwuify: &UwUify, text: &str) -> String {
+        let mut out = String::new();
+        uwuify.uwuify_sentence(text, &mut out).unwrap();
+        out
+    }
+
+    fn words_only() -> UwUConfigBuilder {
+        UwUConfig::builder().faces(0.0).actions(0.0).stutters(0.0)
+    }
+
+    #[test]
+    fn collapse_whitespace() {
+        let uwuify = UwUify::from_config(words_only().build());
+        assert_eq!(
+            self::uwuify(&uwuify, TEXT),
+            "Hewwo thewe, dis is a poem about wowwing hiwws. "
+        );
+    }
+
+    #[test]
+    fn preserve_whitespace() {
+        let config = words_only().whitespace(Whitespace::Preserve).build();
+        let mut uwuify = UwUify::from_config(config.clone());
+        assert_eq!(
+            self::uwuify(&uwuify, TEXT),
+            "Hewwo thewe,\n\n\tdis is a  poem\nabout wowwing hiwws.\n"
+        );
+
+        uwuify.set_config(config.to_builder().words(0.0).build());
+        assert_eq!(self::uwuify(&uwuify, TEXT), TEXT);
+        assert_eq!(self::uwuify(&uwuify, " \n "), " \n ");
+    }
+
+    #[test]
+    fn seed_is_part_of_the_config() {
+        let mut uwuify = UwUify::default();
+        uwuify.set_seed(420);
+
+        assert_eq!(uwuify.config().seed(), 420);
+        assert_eq!(
+            self::uwuify(&uwuify, TEXT),
+            self::uwuify(
+                &UwUify::from_config(UwUConfig::builder().seed(420).build()),
+                TEXT
+            )
+        );
+    }
+
+    /// Pins the exact output for fixed seeds. If any of these change, every output anyone has
+    /// saved changes with them
+    #[test]
+    fn golden() {
+        for (seed, expected) in [
+            (
+                69,
+                "Hewwo thewe! I weawwy w-wove (✧∀✧)/ *teleports behind you* w-wowwing down the \
+                hiwws nyeaw my house, nyo mattew the weathew. ",
+            ),
+            (
+                420,
+                "H-Hewwo *bites lips* t-thewe! I (≧◡≦) *shuffles closer* w-weawwy wove (o^▽^o) \
+                *screams* w-wowwing d-down the hiwws nyeaw my house, nyo m-mattew the weathew. ",
+            ),
+            (
+                u64::MAX,
+                "Hewwo thewe! I weawwy wove wowwing down the hiwws nyeaw my h-house, nyo mattew \
+                the weathew. ",
+            ),
+        ] {
+            let uwuify = UwUify::from_config(
+                UwUConfig::builder()
+                    .seed(seed)
+                    .algorithm(AlgorithmVersion::V1)
+                    .build(),
+            );
+            assert_eq!(self::uwuify(&uwuify, GOLDEN), expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn golden_maximum() {
+        let uwuify = UwUify::from_config(
+            UwUConfig::builder()
+                .faces(1.0)
+                .actions(1.0)
+                .stutters(1.0)
+                .seed(7)
+                .algorithm(AlgorithmVersion::V1)
+                .build(),
+        );
+
+        assert_eq!(
+            self::uwuify(&uwuify, GOLDEN),
+            "OwO *looks away* H-Hewwo (^-^*)/ *blushes* t-thewe! (￣ω￣) *screams* I-I (;;;*_*) \
+            *shuffles closer* w-weawwy (°ㅂ°╬) *flushes* w-wove (≧▽≦)o *moans* w-wowwing (-ω-、) \
+            *moans* d-down ^-^ *sweats* t-the >w< *cries* h-hiwws (☆ω☆) *screams* n-nyeaw \
+            (つ≧▽≦)つ *bites lips* m-my (・_・ヾ *cuddles you* h-house, (\u{3000}･ω･)☞ *cries* \
+            n-nyo (♡°▽°♡) *pokes you* m-mattew ^-^ *sweats* t-the ~(>_<~) *screams* w-weathew. "
+        );
+    }
+
+    #[test]
+    fn golden_v2() {
+        let config = UwUConfig::builder()
+            .words(0.7)
+            .faces(0.3)
+            .actions(0.3)
+            .stutters(0.3)
+            .algorithm(AlgorithmVersion::V2);
+
+        for (seed, expected) in [
+            (
+                69,
+                "*sweats* Hewwo *looks at you* t-thewe! I-I r-really (´ ∀ ` *) *bites lips* wove \
+                (✧∀✧)/ wowwing down the hills *hugs tightly* near *sighs* m-my x3 house, n-nyo \
+                m-mattew the \\(★ω★)/ weathew. ",
+            ),
+            (
+                420,
+                "(✧∀✧)/ H-Hewwo ( =ω= ) *twerks* thewe! *looks away* I (≧◡≦) *shuffles closer* \
+                w-weawwy love (o^▽^o) *bites lips* rolling ( =ω=)..nyaa down *hugs tightly* the \
+                hills n-near my house, n-nyo (⌒ω⌒) matter *hugs tightly* the *notices bulge* \
+                w-weathew. ",
+            ),
+        ] {
+            let uwuify = UwUify::from_config(config.clone().seed(seed).build());
+            assert_eq!(self::uwuify(&uwuify, GOLDEN), expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn independent_draws() {
+        let text: String = (0..500).map(|i| format!("word{i} ")).collect();
+        let config = UwUConfig::builder().faces(0.2).actions(0.4).stutters(0.6);
+
+        for (algorithm, independent) in
+            [(AlgorithmVersion::V1, false), (AlgorithmVersion::V2, true)]
+        {
+            let uwuify = UwUify::from_config(config.clone().algorithm(algorithm).build());
+            let words: Vec<_> = uwuify
+                .uwuify_iter(&text)
+                .filter_map(|token| match token {
+                    Token::Word(word) => Some(word),
+                    Token::Space(_) | Token::Decoration(_) => None,
+                })
+                .collect();
+
+            let face_alone = words
+                .iter()
+                .any(|word| word.face.is_some() && (word.action.is_none() || !word.stutter));
+            assert_eq!(face_alone, independent, "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn seeding() {
+        let config = UwUConfig::builder()
+            .faces(0.5)
+            .actions(0.5)
+            .stutters(0.5)
+            .builtin_dictionary(false);
+        let text = "the the the the the the the the";
+
+        let uwuify = UwUify::from_config(config.clone().build());
+        assert_eq!(self::uwuify(&uwuify, text), format!("{text} "));
+
+        let uwuify = UwUify::from_config(config.clone().seeding(Seeding::Position).build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "(≧◡≦) *leans over* t-the (✧∀✧)/ the (>∀<☆)ノ t-the (⌒ω⌒) *leans over* t-the \
+            (o^▽^o) t-the (✧∀✧)/ the *pokes you* t-the (☆ω☆) the "
+        );
+
+        // Only the last word has different neighbours
+        let uwuify = UwUify::from_config(config.seeding(Seeding::Context).build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "the the the the the the the (*´▽`*) the "
+        );
+    }
+
+    #[test]
+    fn golden_seeding() {
+        let config = UwUConfig::builder()
+            .words(0.7)
+            .faces(0.3)
+            .actions(0.3)
+            .stutters(0.3)
+            .algorithm(AlgorithmVersion::V2);
+
+        for (seeding, expected) in [
+            (
+                Seeding::Position,
+                "Hello (⌒ω⌒) thewe! *shuffles closer* I weawwy w-wove wowwing (^-^*)/ down the \
+                (=^-ω-^=) hiwws (☆▽☆) *cries* n-nyeaw my {{ (>_<) }} house, *twerks* no mattew \
+                *hugs tightly* the w-weathew. ",
+            ),
+            (
+                Seeding::Context,
+                "*shuffles closer* Hewwo thewe! *twerks* I (つ✧ω✧)つ weawwy love *screams* wowwing \
+                ( ╥ω╥ ) down (*≧ω≦*) the (°ㅂ°╬) hiwws (/ =ω=)/ *teleports behind you* nyeaw \
+                *screams* my house, *teleports behind you* no (=^‥^=) m-mattew *teleports behind \
+                you* the (＞ｍ＜) *hugs tightly* w-weather. ",
+            ),
+        ] {
+            let uwuify = UwUify::from_config(config.clone().seeding(seeding).build());
+            assert_eq!(self::uwuify(&uwuify, GOLDEN), expected, "{seeding:?}");
+        }
+    }
+
+    #[test]
+    fn golden_v3() {
+        let uwuify = UwUify::from_config(
+            UwUConfig::builder()
+                .words(0.7)
+                .faces(0.3)
+                .actions(0.3)
+                .stutters(0.3)
+                .algorithm(AlgorithmVersion::V3)
+                .build(),
+        );
+
+        assert_eq!(
+            self::uwuify(&uwuify, GOLDEN),
+            "*sweats* Hewwo *looks at you* t-thewe! I-I r-really (´ ∀ ` *) *bites lips* wuv \
+            (✧∀✧)/ wowwing down da hills *hugs tightly* near *sighs* m-my x3 house, n-nyo \
+            m-mattew da \\(★ω★)/ weathew. "
+        );
+    }
+
+    #[test]
+    fn dictionary() {
+        let config = words_only().replace_word("cat", "kitty");
+        let text = "What, YOU love The cat?";
+
+        let uwuify = UwUify::from_config(config.clone().build());
+        assert_eq!(self::uwuify(&uwuify, text), "Wut, CHU wuv Da kitty? ");
+
+        let uwuify = UwUify::from_config(config.clone().builtin_dictionary(false).build());
+        assert_eq!(self::uwuify(&uwuify, text), "What, YOU wove The kitty? ");
+
+        let uwuify = UwUify::from_config(config.words(0.0).build());
+        assert_eq!(self::uwuify(&uwuify, text), format!("{text} "));
+    }
+
+    #[test]
+    fn punctuation() {
+        let text = "\"(hello) world. ... the\" -- \"Love\"!";
+        let config = UwUConfig::builder()
+            .faces(1.0)
+            .actions(0.0)
+            .stutters(1.0)
+            .seed(1);
+
+        let uwuify = UwUify::from_config(config.clone().build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "(^◔ᴥ◔^) \"(h-hewwo) ( =ω= ) w-wowwd. ... (;;;*_*) d-da\" -- xDD \"W-Wuv\"! "
+        );
+
+        // Every face is as likely before V7
+        let uwuify = UwUify::from_config(config.clone().algorithm(AlgorithmVersion::V6).build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "(=^‥^=) \"(h-hewwo) (/ =ω=)/ w-wowwd. ... (^-^*)/ d-da\" -- >_> \"W-Wuv\"! "
+        );
+
+        let uwuify = UwUify::from_config(config.algorithm(AlgorithmVersion::V3).build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "(=^‥^=) \"-\"(hewwo) (/ =ω=)/ w-wowwd. :3 .-... (^-^*)/ d-da\" ( ╥ω╥ ) ---- >_> \
+            \"-\"Wuv\"! "
+        );
+    }
+
+    #[test]
+    fn graphemes() {
+        // Both with a combining accent instead of a precomposed letter
+        let text = "E\u{301}cole cafe\u{301}!";
+        let config = words_only().stutters(1.0);
+
+        let uwuify = UwUify::from_config(config.clone().build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "E\u{301}-E\u{301}cowe c-cafe\u{301}! "
+        );
+
+        let uwuify = UwUify::from_config(config.algorithm(AlgorithmVersion::V4).build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "E-E\u{301}cowe c-cafe\u{301}! "
+        );
+    }
+
+    #[test]
+    fn languages() {
+        let text = "Привет, hello らりるれろ!";
+        let config = words_only().whitespace(Whitespace::Preserve);
+
+        let uwuify = UwUify::from_config(config.clone().build());
+        assert_eq!(self::uwuify(&uwuify, text), "Пвивет, hewwo わゐうゑを!");
+
+        let uwuify = UwUify::from_config(config.clone().language(Language::Spanish).build());
+        assert_eq!(self::uwuify(&uwuify, text), "Привет, heyo らりるれろ!");
+
+        let uwuify = UwUify::from_config(config.algorithm(AlgorithmVersion::V5).build());
+        assert_eq!(self::uwuify(&uwuify, text), "Привет, hewwo らりるれろ!");
+    }
+
+    #[test]
+    fn segmentation() {
+        let text = "私はラーメンが好き。 see https://例え.jp/ラーメン";
+        let config = words_only().stutters(1.0).whitespace(Whitespace::Preserve);
+
+        let uwuify = UwUify::from_config(config.clone().build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "私-私はワーメンが好き。 s-see h-https://例え.jp/ラーメン"
+        );
+
+        let uwuify = UwUify::from_config(config.segmentation(Segmentation::Unicode).build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "私-私は-はワ-ワーメンが-が好-好き-き。 s-see h-https://例え.jp/ラーメン"
+        );
+
+        let words = uwuify
+            .uwuify_iter("私はラーメンが好き")
+            .filter(|token| matches!(token, Token::Word(_)))
+            .count();
+        assert_eq!(words, 6);
+
+        // Thai has no dictionary segmenter, so a run of it stays one word
+        let words = uwuify
+            .uwuify_iter("ฉันชอบราเมน มาก")
+            .filter(|token| matches!(token, Token::Word(_)))
+            .count();
+        assert_eq!(words, 2);
+    }
+
+    #[test]
+    fn sentences() {
+        let text = "Hello there, this is a poem. It rolls... 本当？ ok";
+        let config = words_only()
+            .sentence_faces(1.0)
+            .sentence_actions(1.0)
+            .whitespace(Whitespace::Preserve);
+
+        let uwuify = UwUify::from_config(config.clone().build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "Hewwo thewe, (-ω-、) dis is a poem. (o´∀`o) *hugs tightly* It wowws... ⊂(･ω･*⊂) \
+            *pokes you* 本当？ :33 *flushes* ok"
+        );
+
+        // Only the per-word probabilities put anything before a word
+        let uwuify = UwUify::from_config(config.faces(1.0).sentence_actions(0.0).build());
+        assert_eq!(
+            self::uwuify(&uwuify, "Hi. There"),
+            "UwU Hai. (＾▽＾) (￣ω￣) Thewe"
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let text = "a b c d e f g. h i j\n\nk l m";
+        let config = UwUConfig::builder()
+            .words(0.0)
+            .faces(1.0)
+            .actions(0.0)
+            .stutters(0.0)
+            .whitespace(Whitespace::Preserve);
+        let decorated = |config: UwUConfigBuilder| {
+            let uwuify = UwUify::from_config(config.build());
+            let words: Vec<_> = uwuify
+                .uwuify_iter(text)
+                .filter_map(|token| match token {
+                    Token::Word(word) => Some(word),
+                    Token::Space(_) | Token::Decoration(_) => None,
+                })
+                .collect();
+            (0..words.len())
+                .filter(|&i| words[i].face.is_some())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(decorated(config.clone()), (0..13).collect::<Vec<_>>());
+        assert_eq!(decorated(config.clone().cooldown(2)), [0, 3, 6, 9, 12]);
+        assert_eq!(
+            decorated(config.clone().cooldown(2).burst(2)),
+            [0, 1, 4, 5, 8, 9, 12]
+        );
+        assert_eq!(
+            decorated(config.clone().max_per_sentence(Some(1))),
+            [0, 7, 10]
+        );
+        assert_eq!(
+            decorated(config.clone().max_per_paragraph(Some(2))),
+            [0, 1, 10, 11]
+        );
+
+        // Nothing but the faces and actions change
+        let words = |config: UwUConfigBuilder| {
+            let uwuify = UwUify::from_config(config.words(1.0).stutters(0.5).build());
+            uwuify
+                .uwuify_iter(text)
+                .filter_map(|token| match token {
+                    Token::Word(word) => Some((word.word, word.stutter)),
+                    Token::Space(_) | Token::Decoration(_) => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(words(config.clone()), words(config.cooldown(5)));
+    }
+
+    #[test]
+    fn custom_decorations() {
+        let config = words_only()
+            .words(0.0)
+            .faces(1.0)
+            .actions(1.0)
+            .builtin_faces(false)
+            .builtin_actions(false)
+            .custom_face(Decoration::custom("(ﾉ◕ヮ◕)ﾉ  ", 1))
+            .custom_action(Decoration::custom("*pats <you>*", 1));
+
+        let uwuify = UwUify::from_config(config.clone().build());
+        assert_eq!(
+            self::uwuify(&uwuify, "hi senpai"),
+            "(ﾉ◕ヮ◕)ﾉ *pats <you>* hi (ﾉ◕ヮ◕)ﾉ *pats <you>* senpai "
+        );
+
+        let uwuify = UwUify::from_config(config.mode(InputMode::Html).build());
+        assert_eq!(
+            self::uwuify(&uwuify, "hi"),
+            "(ﾉ◕ヮ◕)ﾉ *pats &lt;you&gt;* hi"
+        );
+    }
+
+    #[test]
+    fn tokens() {
+        let config = words_only()
+            .words(1.0)
+            .faces(1.0)
+            .actions(1.0)
+            .stutters(1.0)
+            .sentence_faces(1.0)
+            .builtin_faces(false)
+            .builtin_actions(false)
+            .custom_face(Decoration::custom("(ﾉ◕ヮ◕)ﾉ  ", 1))
+            .custom_action(Decoration::custom("*pats*", 1));
+        let uwuify = UwUify::from_config(config.build());
+
+        let Some(Token::Word(word)) = uwuify.uwuify_iter("(hello!").next() else {
+            panic!("not a word");
+        };
+        assert_eq!(word.text(), "(hewwo!");
+        assert!(word.stutter());
+        assert_eq!(word.face(), Some("(ﾉ◕ヮ◕)ﾉ"));
+        assert_eq!(word.action(), Some("*pats*"));
+        assert_eq!(word.end_face(), Some("(ﾉ◕ヮ◕)ﾉ"));
+        assert_eq!(word.end_action(), None);
+    }
+
+    #[test]
+    fn existing_decorations() {
+        let text = "*hugs tightly* h-hello OwO there ( ╥ω╥ )\n\nfriend";
+        let config = words_only().stutters(1.0).whitespace(Whitespace::Preserve);
+
+        let uwuify = UwUify::from_config(config.clone().build());
+        assert_eq!(
+            self::uwuify(&uwuify, text),
+            "*hugs tightly* h-hewwo OwO t-thewe ( ╥ω╥ )\n\nf-fwiend"
+        );
+
+        // Older versions read them as words
+        let v7 = UwUify::from_config(config.algorithm(AlgorithmVersion::V7).build());
+        assert_eq!(
+            self::uwuify(&v7, text),
+            "*h-hugs t-tightwy* h-h-hewwo O-OwO t-thewe ( ╥ω-ω╥ )\n\nf-fwiend"
+        );
+
+        assert_eq!(super::unstutter("h-hewwo"), Some("hewwo"));
+        assert_eq!(super::unstutter("x-ray"), None);
+    }
+
+    #[test]
+    fn existing_decorations_count() {
+        let config = words_only().faces(1.0).max_per_sentence(Some(1));
+
+        // The face that's already there uses up the sentence
+        let uwuify = UwUify::from_config(config.build());
+        let out = self::uwuify(&uwuify, "OwO hello there. Hi friend.");
+        assert!(out.starts_with("OwO hewwo thewe. "), "{out}");
+        assert_eq!(out.matches(' ').count(), 6, "{out}");
+
+        // A word with one in front of it doesn't get another
+        let uwuify = UwUify::from_config(words_only().faces(1.0).actions(1.0).build());
+        let out = self::uwuify(&uwuify, "*hugs tightly* hi");
+        assert_eq!(out, "*hugs tightly* hai ");
+    }
+
+    #[test]
+    fn passes() {
+        let config = UwUConfig::builder()
+            .faces(0.3)
+            .actions(0.3)
+            .stutters(0.5)
+            .seed(420)
+            .whitespace(Whitespace::Preserve);
+        let once = |config: UwUConfigBuilder, text: &str| {
+            self::uwuify(&UwUify::from_config(config.build()), text)
+        };
+
+        // Each pass is a plain pass over the last one, with the next seed
+        let first = once(config.clone(), GOLDEN);
+        let second = once(config.clone().seed(421), &first);
+        let third = once(config.clone().seed(422), &second);
+        assert_eq!(once(config.clone().passes(3), GOLDEN), third);
+        assert_eq!(once(config.clone().passes(0), GOLDEN), first);
+
+        // Nothing from the passes before is changed or stacked
+        let words = |text: &str| text.split_whitespace().count();
+        assert!(words(&first) <= words(&second) && words(&second) <= words(&third));
+        for text in [&second, &third] {
+            assert!(!text.contains("-h-") && !text.contains("-w-"), "{text}");
+        }
+        let uwuify = UwUify::from_config(config.build());
+        for decoration in uwuify.written.iter().filter(|d| first.contains(d.as_str())) {
+            assert!(third.contains(decoration.as_str()), "{decoration}");
+        }
+    }
+
+    #[test]
+    fn sentiment() {
+        let config = words_only()
+            .words(0.0)
+            .faces(1.0)
+            .actions(0.0)
+            .stutters(0.0)
+            .builtin_faces(false)
+            .custom_face(Decoration::custom("yay", 1).with_categories(vec![Category::Happy]))
+            .custom_face(Decoration::custom("qwq", 1).with_categories(vec![Category::Sad]));
+        let text = "I miss you, I'm so sad.\n\nBut today is great, I love it!";
+
+        let count = |config: UwUConfigBuilder| {
+            let (mut sad, mut happy) = ((0, 0), (0, 0));
+            for seed in 0..20 {
+                let uwuify = UwUify::from_config(config.clone().seed(seed).build());
+                let out = self::uwuify(&uwuify, text);
+                let (first, second) = out.split_once("sad.").unwrap();
+                sad.0 += first.matches("qwq").count();
+                sad.1 += first.matches("yay").count();
+                happy.0 += second.matches("yay").count();
+                happy.1 += second.matches("qwq").count();
+            }
+            (sad, happy)
+        };
+
+        // Matching faces are 8 times as likely, so they're nearly all of them
+        let (sad, happy) = count(config.clone().sentiment(true));
+        assert!(sad.0 > sad.1 * 4, "{sad:?}");
+        assert!(happy.0 > happy.1 * 4, "{happy:?}");
+
+        // Scoring sentences only changes which faces are picked, not where they go
+        let (plain_sad, plain_happy) = count(config);
+        assert_eq!(sad.0 + sad.1, plain_sad.0 + plain_sad.1);
+        assert_eq!(happy.0 + happy.1, plain_happy.0 + plain_happy.1);
+        assert!(plain_sad.0 < plain_sad.1 * 4, "{plain_sad:?}");
+    }
+
+    #[test]
+    fn custom_rules() {
+        let config = words_only().whitespace(Whitespace::Preserve);
+        let rule = Rule::literal("poem", "powem").with_case(Case::Preserve);
+
+        let uwuify = UwUify::from_config(config.clone().rule(rule.clone()).build());
+        assert_eq!(
+            self::uwuify(&uwuify, TEXT),
+            "Hewwo thewe,\n\n\tdis is a  powem\nabout wowwing hiwws.\n"
+        );
+
+        let uwuify = UwUify::from_config(config.rule(rule).builtin_rules(false).build());
+        assert_eq!(
+            self::uwuify(&uwuify, TEXT),
+            "Hello there,\n\n\tdis is a  powem\nabout rolling hills.\n"
+        );
+    }
+
+    #[cfg(uwu_bench)]
+    #[bench]
+    fn uwu_bench(b: &mut test::Bencher) {
+        let uwuify = UwUify::default();
+        let text = TEXT.repeat(1000);
+        b.iter(|| self::uwuify(&uwuify, &text));
+    }
+}
@@ -1,113 +0,0 @@
-pub const MIXED_FACES: [&str; 89] = [
-    "OwO ",
-    "UwU ",
-    ">w< ",
-    "^w^ ",
-    "^-^ ",
-    ":3 ",
-    "x3 ",
-    "xDD ",
-    ";;w;; ",
-    ">_< ",
-    ">_> ",
-    "^.^ ",
-    ":33 ",
-    "uWu ",
-    "(o^ ^o) ",
-    "(*^.^*) ",
-    "o(>< )o ",
-    "(>_<) ",
-    "~(>_<~) ",
-    "(;;;*_*) ",
-    "{{ (>_<) }} ",
-    "(^-^*)/ ",
-    "( ~*-*)~ ",
-    "(* ^ ω ^) ",
-    "(´ ∀ ` *) ",
-    "(o^▽^o) ",
-    "(⌒▽⌒)☆ ",
-    "(・∀・)ﾉ ",
-    "(￣ω￣) ",
-    "(o･ω･o) ",
-    "(^人^) ",
-    "(*´▽`*) ",
-    "(≧◡≦) ",
-    "(o´∀`o) ",
-    "(＾▽＾) ",
-    "(⌒ω⌒) ",
-    "╰(▔∀▔)╯ ",
-    "(*^‿^*) ",
-    "(✯◡✯) ",
-    "(*≧ω≦*) ",
-    "(☆▽☆) ",
-    "＼(≧▽≦)／ ",
-    "(*°▽°*) ",
-    "(✧ω✧) ",
-    "(>∀<☆)ノ ",
-    "(≧▽≦)o ",
-    "(☆ω☆) ",
-    "(っ˘ω˘ς ) ",
-    "\\(★ω★)/ ",
-    "(╯✧▽✧)╯ ",
-    "(>ω<)o ",
-    "(´ ω `♡) ",
-    "(♡°▽°♡) ",
-    "♡(｡- ω -) ",
-    "(❤ω❤) ",
-    "(*ﾉωﾉ) ",
-    "(＃￣ω￣) ",
-    "(＞ｍ＜) ",
-    "(ᗒᗣᗕ)՞ ",
-    "(＃`Д´) ",
-    "(°ㅂ°╬) ",
-    "(╬ Ò﹏Ó) ",
-    "(´-ω-`) ",
-    "(-ω-、) ",
-    "( ╥ω╥ ) ",
-    "(ノωヽ) ",
-    "(・_・ヾ ",
-    "╮(￣ω￣;)╭ ",
-    "(*・ω・)ﾉ ",
-    "(✧∀✧)/ ",
-    "(つ≧▽≦)つ ",
-    "(つ✧ω✧)つ ",
-    "⊂(･ω･*⊂) ",
-    "(^ω~) ",
-    "|･ω･) ",
-    "(=^･ω･^=) ",
-    "(=^･ｪ･^=) ",
-    "(=①ω①=) ",
-    "( =ω=)..nyaa ",
-    "(=`ω´=) ",
-    "(=^‥^=) ",
-    "( =ノωヽ=) ",
-    "(=^-ω-^=) ",
-    "(=`ω´=)ノ” ",
-    "(/ =ω=)/ ",
-    "(•ㅅ•❀)ฅ ",
-    "( =ω= ) ",
-    "(^◔ᴥ◔^) ",
-    "(　･ω･)☞ ",
-];
-
-pub const ACTIONS: [&str; 19] = [
-    "*notices bulge* ",
-    "*cries* ",
-    "*hugs tightly* ",
-    "*screams* ",
-    "*looks away* ",
-    "*blushes* ",
-    "*sweats* ",
-    "*cuddles you* ",
-    "*moans* ",
-    "*giggles shyly* ",
-    "*looks at you* ",
-    "*twerks* ",
-    "*sighs* ",
-    "*leans over* ",
-    "*pokes you* ",
-    "*teleports behind you* ",
-    "*shuffles closer* ",
-    "*flushes* ",
-    "*bites lips* ",
-];
@@ -1,37 +1,655 @@
 use std::sync::Arc;
 
-mod constants;
-mod uwu;
-
 use dominator::{clone, events, html, with_node, Dom};
-use futures_signals::signal::Mutable;
-use uwu::UwUify;
+use futures_signals::signal::{Mutable, SignalExt};
+use futures_signals::signal_vec::{MutableVec, SignalVecExt};
+use uwuifyy_core::{
+    parse_seed, random_seed, AlgorithmVersion, Anchor, Case, Category, Decoration, InputMode,
+    Language, PresetFormat, Rule, RuleError, RuleKind, Seeding, Segmentation, UwUConfig, UwUify,
+    Whitespace,
+};
 use wasm_bindgen::prelude::*;
-use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use web_sys::{File, HtmlAnchorElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 
 struct App {
     output: Mutable<String>,
     input: Mutable<String>,
-    uwuify: UwUify,
+    words: Mutable<f64>,
+    faces: Mutable<f64>,
+    actions: Mutable<f64>,
+    stutters: Mutable<f64>,
+    sentence_faces: Mutable<f64>,
+    sentence_actions: Mutable<f64>,
+    cooldown: Mutable<u32>,
+    burst: Mutable<u32>,
+    max_per_sentence: Mutable<Option<u32>>,
+    max_per_paragraph: Mutable<Option<u32>>,
+    categories: Mutable<Vec<Category>>,
+    sfw: Mutable<bool>,
+    sentiment: Mutable<bool>,
+    builtin_faces: Mutable<bool>,
+    custom_faces: MutableVec<Arc<DecorationRow>>,
+    builtin_actions: Mutable<bool>,
+    custom_actions: MutableVec<Arc<DecorationRow>>,
+    mode: Mutable<InputMode>,
+    passes: Mutable<u32>,
+    /// Kept so imported presets are UwUified with the version they were made with
+    algorithm: Mutable<AlgorithmVersion>,
+    /// The seed as typed in, see [`parse_seed`]
+    seed: Mutable<String>,
+    seeding: Mutable<Seeding>,
+    /// Kept as it is unless an imported preset says otherwise, there's nothing to change it
+    whitespace: Mutable<Whitespace>,
+    segmentation: Mutable<Segmentation>,
+    language: Mutable<Language>,
+    builtin_rules: Mutable<bool>,
+    rules: MutableVec<Arc<RuleRow>>,
+    builtin_dictionary: Mutable<bool>,
+    dictionary: MutableVec<Arc<DictionaryRow>>,
+}
+
+/// A custom rule as it's being edited
+struct RuleRow {
+    find: Mutable<String>,
+    replace: Mutable<String>,
+    pattern: Mutable<bool>,
+    anchor: Mutable<Anchor>,
+    case: Mutable<Case>,
+    priority: Mutable<i32>,
+    /// Why the rule can't be used, if it can't
+    error: Mutable<Option<String>>,
+}
+
+impl RuleRow {
+    fn new(rule: &Rule) -> Arc<Self> {
+        Arc::new(RuleRow {
+            find: Mutable::new(rule.find().to_owned()),
+            replace: Mutable::new(rule.replace().to_owned()),
+            pattern: Mutable::new(rule.kind() == RuleKind::Pattern),
+            anchor: Mutable::new(rule.anchor()),
+            case: Mutable::new(rule.case()),
+            priority: Mutable::new(rule.priority()),
+            error: Mutable::new(None),
+        })
+    }
+
+    fn rule(&self) -> Result<Rule, RuleError> {
+        let find = self.find.get_cloned();
+        let replace = self.replace.get_cloned();
+        let rule = if self.pattern.get() {
+            Rule::pattern(find, replace)?
+        } else {
+            Rule::literal(find, replace)
+        };
+
+        Ok(rule
+            .with_anchor(self.anchor.get())
+            .with_case(self.case.get())
+            .with_priority(self.priority.get()))
+    }
+
+    fn render(rule: Arc<Self>, app: Arc<App>) -> Dom {
+        html!("tr", {
+            .children(&mut [
+                html!("td", {
+                    .child(html!("input" => HtmlInputElement, {
+                        .class("u-full-width")
+                        .attr("type", "text")
+                        .prop_signal("value", rule.find.signal_cloned())
+
+                        .with_node!(element => {
+                            .event(clone!(app, rule => move |_: events::Input| {
+                                rule.find.set_neq(element.value());
+                                app.uwuify();
+                            }))
+                        })
+                    }))
+
+                    .child_signal(rule.error.signal_cloned().map(|error| {
+                        error.map(|error| html!("small", {
+                            .style("display", "block")
+                            .style("color", "red")
+                            .text(&error)
+                        }))
+                    }))
+                }),
+
+                html!("td", {
+                    .child(html!("input" => HtmlInputElement, {
+                        .class("u-full-width")
+                        .attr("type", "text")
+                        .prop_signal("value", rule.replace.signal_cloned())
+
+                        .with_node!(element => {
+                            .event(clone!(app, rule => move |_: events::Input| {
+                                rule.replace.set_neq(element.value());
+                                app.uwuify();
+                            }))
+                        })
+                    }))
+                }),
+
+                html!("td", {
+                    .child(html!("input" => HtmlInputElement, {
+                        .attr("type", "checkbox")
+                        .prop_signal("checked", rule.pattern.signal())
+
+                        .with_node!(element => {
+                            .event(clone!(app, rule => move |_: events::Change| {
+                                rule.pattern.set_neq(element.checked());
+                                app.uwuify();
+                            }))
+                        })
+                    }))
+                }),
+
+                html!("td", {
+                    .child(html!("select" => HtmlSelectElement, {
+                        .children(Anchor::ALL.iter().copied().map(|anchor| {
+                            html!("option", {
+                                .prop_signal("selected", rule.anchor.signal_ref(move |selected| *selected == anchor))
+                                .text(anchor.name())
+                            })
+                        }))
+
+                        .with_node!(element => {
+                            .event(clone!(app, rule => move |_: events::Change| {
+                                if let Some(anchor) = Anchor::ALL.get(element.selected_index() as usize) {
+                                    rule.anchor.set_neq(*anchor);
+                                    app.uwuify();
+                                }
+                            }))
+                        })
+                    }))
+                }),
+
+                html!("td", {
+                    .child(html!("select" => HtmlSelectElement, {
+                        .children(Case::ALL.iter().copied().map(|case| {
+                            html!("option", {
+                                .prop_signal("selected", rule.case.signal_ref(move |selected| *selected == case))
+                                .text(case.name())
+                            })
+                        }))
+
+                        .with_node!(element => {
+                            .event(clone!(app, rule => move |_: events::Change| {
+                                if let Some(case) = Case::ALL.get(element.selected_index() as usize) {
+                                    rule.case.set_neq(*case);
+                                    app.uwuify();
+                                }
+                            }))
+                        })
+                    }))
+                }),
+
+                html!("td", {
+                    .child(html!("input" => HtmlInputElement, {
+                        .style("width", "5em")
+                        .attr("type", "number")
+                        .attr("step", "1")
+                        .prop_signal("value", rule.priority.signal_ref(|priority| priority.to_string()))
+
+                        .with_node!(element => {
+                            .event(clone!(app, rule => move |_: events::Input| {
+                                rule.priority.set_neq(element.value().parse().unwrap_or(0));
+                                app.uwuify();
+                            }))
+                        })
+                    }))
+                }),
+
+                html!("td", {
+                    .child(html!("button", {
+                        .event(clone!(app, rule => move |_: events::Click| {
+                            app.rules.lock_mut().retain(|row| !Arc::ptr_eq(row, &rule));
+                            app.uwuify();
+                        }))
+
+                        .text("Remove")
+                    }))
+                }),
+            ])
+        })
+    }
+}
+
+/// A custom face or action as it's being edited
+struct DecorationRow {
+    text: Mutable<String>,
+    weight: Mutable<u32>,
+}
+
+impl DecorationRow {
+    fn new(decoration: &Decoration) -> Arc<Self> {
+        Arc::new(DecorationRow {
+            text: Mutable::new(decoration.text().to_owned()),
+            weight: Mutable::new(decoration.weight()),
+        })
+    }
+
+    fn decorations(rows: &MutableVec<Arc<Self>>) -> Vec<Decoration> {
+        rows.lock_ref()
+            .iter()
+            .filter(|row| !row.text.lock_ref().trim().is_empty())
+            .map(|row| Decoration::custom(row.text.get_cloned(), row.weight.get()))
+            .collect()
+    }
+
+    /// `list` is the faces or actions this row is in
+    fn render(row: Arc<Self>, list: MutableVec<Arc<Self>>, app: Arc<App>) -> Dom {
+        html!("tr", {
+            .children(&mut [
+                html!("td", {
+                    .child(html!("input" => HtmlInputElement, {
+                        .class("u-full-width")
+                        .attr("type", "text")
+                        .prop_signal("value", row.text.signal_cloned())
+
+                        .with_node!(element => {
+                            .event(clone!(app, row => move |_: events::Input| {
+                                row.text.set_neq(element.value());
+                                app.uwuify();
+                            }))
+                        })
+                    }))
+                }),
+
+                html!("td", {
+                    .child(html!("input" => HtmlInputElement, {
+                        .style("width", "5em")
+                        .attr("type", "number")
+                        .attr("min", "0")
+                        .attr("step", "1")
+                        .prop_signal("value", row.weight.signal_ref(|weight| weight.to_string()))
+
+                        .with_node!(element => {
+                            .event(clone!(app, row => move |_: events::Input| {
+                                row.weight.set_neq(element.value().parse().unwrap_or(0));
+                                app.uwuify();
+                            }))
+                        })
+                    }))
+                }),
+
+                html!("td", {
+                    .child(html!("button", {
+                        .event(clone!(app, row => move |_: events::Click| {
+                            list.lock_mut().retain(|other| !Arc::ptr_eq(other, &row));
+                            app.uwuify();
+                        }))
+
+                        .text("Remove")
+                    }))
+                }),
+            ])
+        })
+    }
+}
+
+/// A custom dictionary word as it's being edited
+struct DictionaryRow {
+    word: Mutable<String>,
+    replacement: Mutable<String>,
+}
+
+impl DictionaryRow {
+    fn new(word: &str, replacement: &str) -> Arc<Self> {
+        Arc::new(DictionaryRow {
+            word: Mutable::new(word.to_owned()),
+            replacement: Mutable::new(replacement.to_owned()),
+        })
+    }
+
+    fn render(row: Arc<Self>, app: Arc<App>) -> Dom {
+        html!("tr", {
+            .children(&mut [
+                html!("td", {
+                    .child(html!("input" => HtmlInputElement, {
+                        .class("u-full-width")
+                        .attr("type", "text")
+                        .prop_signal("value", row.word.signal_cloned())
+
+                        .with_node!(element => {
+                            .event(clone!(app, row => move |_: events::Input| {
+                                row.word.set_neq(element.value());
+                                app.uwuify();
+                            }))
+                        })
+                    }))
+                }),
+
+                html!("td", {
+                    .child(html!("input" => HtmlInputElement, {
+                        .class("u-full-width")
+                        .attr("type", "text")
+                        .prop_signal("value", row.replacement.signal_cloned())
+
+                        .with_node!(element => {
+                            .event(clone!(app, row => move |_: events::Input| {
+                                row.replacement.set_neq(element.value());
+                                app.uwuify();
+                            }))
+                        })
+                    }))
+                }),
+
+                html!("td", {
+                    .child(html!("button", {
+                        .event(clone!(app, row => move |_: events::Click| {
+                            app.dictionary.lock_mut().retain(|other| !Arc::ptr_eq(other, &row));
+                            app.uwuify();
+                        }))
+
+                        .text("Remove")
+                    }))
+                }),
+            ])
+        })
+    }
 }
 
 impl App {
     fn new() -> Arc<Self> {
+        let mut config = UwUConfig::builder()
+            .whitespace(Whitespace::Preserve)
+            .build();
+        if let Some(saved) = local_storage()
+            .and_then(|storage| storage.get_item(DECORATIONS_KEY).ok()?)
+            .and_then(|preset| UwUConfig::from_preset(&preset, PresetFormat::Json).ok())
+        {
+            config = config
+                .to_builder()
+                .builtin_faces(saved.builtin_faces())
+                .custom_faces(saved.custom_faces().to_vec())
+                .builtin_actions(saved.builtin_actions())
+                .custom_actions(saved.custom_actions().to_vec())
+                .build();
+        }
+
         Arc::new(App {
             input: Mutable::new(String::new()),
             output: Mutable::new(String::new()),
-            uwuify: UwUify::default(),
+            words: Mutable::new(config.words()),
+            faces: Mutable::new(config.faces()),
+            actions: Mutable::new(config.actions()),
+            stutters: Mutable::new(config.stutters()),
+            sentence_faces: Mutable::new(config.sentence_faces()),
+            sentence_actions: Mutable::new(config.sentence_actions()),
+            passes: Mutable::new(config.passes()),
+            cooldown: Mutable::new(config.cooldown()),
+            burst: Mutable::new(config.burst()),
+            max_per_sentence: Mutable::new(config.max_per_sentence()),
+            max_per_paragraph: Mutable::new(config.max_per_paragraph()),
+            categories: Mutable::new(config.categories().to_vec()),
+            sfw: Mutable::new(config.sfw()),
+            sentiment: Mutable::new(config.sentiment()),
+            builtin_faces: Mutable::new(config.builtin_faces()),
+            custom_faces: MutableVec::new_with_values(
+                config
+                    .custom_faces()
+                    .iter()
+                    .map(DecorationRow::new)
+                    .collect(),
+            ),
+            builtin_actions: Mutable::new(config.builtin_actions()),
+            custom_actions: MutableVec::new_with_values(
+                config
+                    .custom_actions()
+                    .iter()
+                    .map(DecorationRow::new)
+                    .collect(),
+            ),
+            mode: Mutable::new(config.mode()),
+            algorithm: Mutable::new(config.algorithm()),
+            seed: Mutable::new(config.seed().to_string()),
+            seeding: Mutable::new(config.seeding()),
+            whitespace: Mutable::new(config.whitespace()),
+            segmentation: Mutable::new(config.segmentation()),
+            language: Mutable::new(config.language()),
+            builtin_rules: Mutable::new(config.builtin_rules()),
+            rules: MutableVec::new(),
+            builtin_dictionary: Mutable::new(config.builtin_dictionary()),
+            dictionary: MutableVec::new(),
         })
     }
 
+    fn config(&self) -> UwUConfig {
+        UwUConfig::builder()
+            .words(self.words.get())
+            .faces(self.faces.get())
+            .actions(self.actions.get())
+            .stutters(self.stutters.get())
+            .sentence_faces(self.sentence_faces.get())
+            .sentence_actions(self.sentence_actions.get())
+            .passes(self.passes.get())
+            .cooldown(self.cooldown.get())
+            .burst(self.burst.get())
+            .max_per_sentence(self.max_per_sentence.get())
+            .max_per_paragraph(self.max_per_paragraph.get())
+            .categories(self.categories.get_cloned())
+            .sfw(self.sfw.get())
+            .sentiment(self.sentiment.get())
+            .builtin_faces(self.builtin_faces.get())
+            .custom_faces(DecorationRow::decorations(&self.custom_faces))
+            .builtin_actions(self.builtin_actions.get())
+            .custom_actions(DecorationRow::decorations(&self.custom_actions))
+            .seed(parse_seed(&self.seed.lock_ref()))
+            .algorithm(self.algorithm.get())
+            .seeding(self.seeding.get())
+            .segmentation(self.segmentation.get())
+            .language(self.language.get())
+            .builtin_rules(self.builtin_rules.get())
+            .rules(self.rules())
+            .builtin_dictionary(self.builtin_dictionary.get())
+            .dictionary(
+                self.dictionary
+                    .lock_ref()
+                    .iter()
+                    .filter(|row| !row.word.lock_ref().is_empty())
+                    .map(|row| (row.word.get_cloned(), row.replacement.get_cloned()))
+                    .collect(),
+            )
+            .whitespace(self.whitespace.get())
+            .mode(self.mode.get())
+            .build()
+    }
+
+    fn set_config(&self, config: &UwUConfig) {
+        self.words.set_neq(config.words());
+        self.faces.set_neq(config.faces());
+        self.actions.set_neq(config.actions());
+        self.stutters.set_neq(config.stutters());
+        self.sentence_faces.set_neq(config.sentence_faces());
+        self.sentence_actions.set_neq(config.sentence_actions());
+        self.passes.set_neq(config.passes());
+        self.cooldown.set_neq(config.cooldown());
+        self.burst.set_neq(config.burst());
+        self.max_per_sentence.set_neq(config.max_per_sentence());
+        self.max_per_paragraph.set_neq(config.max_per_paragraph());
+        self.categories.set_neq(config.categories().to_vec());
+        self.sfw.set_neq(config.sfw());
+        self.sentiment.set_neq(config.sentiment());
+        self.builtin_faces.set_neq(config.builtin_faces());
+        self.custom_faces.lock_mut().replace_cloned(
+            config
+                .custom_faces()
+                .iter()
+                .map(DecorationRow::new)
+                .collect(),
+        );
+        self.builtin_actions.set_neq(config.builtin_actions());
+        self.custom_actions.lock_mut().replace_cloned(
+            config
+                .custom_actions()
+                .iter()
+                .map(DecorationRow::new)
+                .collect(),
+        );
+        self.mode.set_neq(config.mode());
+        self.algorithm.set_neq(config.algorithm());
+        self.seed.set_neq(config.seed().to_string());
+        self.seeding.set_neq(config.seeding());
+        self.whitespace.set_neq(config.whitespace());
+        self.segmentation.set_neq(config.segmentation());
+        self.language.set_neq(config.language());
+        self.builtin_rules.set_neq(config.builtin_rules());
+        self.rules
+            .lock_mut()
+            .replace_cloned(config.rules().iter().map(RuleRow::new).collect());
+        self.builtin_dictionary.set_neq(config.builtin_dictionary());
+        self.dictionary.lock_mut().replace_cloned(
+            config
+                .dictionary()
+                .iter()
+                .map(|(word, replacement)| DictionaryRow::new(word, replacement))
+                .collect(),
+        );
+    }
+
+    /// The custom rules that can be used, the others are marked with why they can't
+    fn rules(&self) -> Vec<Rule> {
+        self.rules
+            .lock_ref()
+            .iter()
+            .filter_map(|row| match row.rule() {
+                Ok(rule) => {
+                    row.error.set_neq(None);
+                    Some(rule)
+                }
+                Err(e) => {
+                    row.error.set_neq(Some(e.to_string()));
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn export_preset(&self) {
+        let format = PresetFormat::Json;
+        let preset = self.config().to_preset(format);
+
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            let link: HtmlAnchorElement = document.create_element("a").unwrap().unchecked_into();
+            link.set_href(&format!(
+                "data:application/json;charset=utf-8,{}",
+                js_sys::encode_uri_component(&preset)
+            ));
+            link.set_download(&format!("webuwu.{}", format.extension()));
+            link.click();
+        }
+    }
+
+    async fn import_preset(&self, file: File) {
+        let preset = wasm_bindgen_futures::JsFuture::from(file.text())
+            .await
+            .ok()
+            .and_then(|text| text.as_string())
+            .unwrap_or_default();
+
+        // Like the CLI, whatever the preset doesn't set is kept, so whitespace is still kept as
+        // it is unless the preset collapses it
+        match UwUConfig::from_preset_over(
+            &preset,
+            PresetFormat::from_file_name(&file.name()),
+            &self.config(),
+        ) {
+            Ok(config) => {
+                self.set_config(&config);
+                self.uwuify();
+            }
+            Err(e) => {
+                if let Some(window) = web_sys::window() {
+                    let _ = window.alert_with_message(&format!("{}: {e}", file.name()));
+                }
+            }
+        }
+    }
+
     fn uwuify(&self) {
+        let config = self.config();
+        save_decorations(&config);
+
         let mut new = String::new();
-        self.uwuify
-            .uwuify_sentence(&*self.input.lock_ref(), &mut new)
+        UwUify::from_config(config)
+            .uwuify_sentence(&self.input.lock_ref(), &mut new)
             .unwrap();
         self.output.set_neq(new);
     }
 
+    /// Turns the input back into plain text, as if it was UwUified with the current settings
+    fn deuwuify(&self) {
+        let mut plain = String::new();
+        UwUify::from_config(self.config())
+            .deuwuify(&self.input.lock_ref(), &mut plain)
+            .unwrap();
+        self.input.set_neq(plain);
+        self.uwuify();
+    }
+
+    fn render_decorations(
+        app: &Arc<Self>,
+        name: &str,
+        one: &str,
+        builtin: &Mutable<bool>,
+        list: &MutableVec<Arc<DecorationRow>>,
+    ) -> Dom {
+        html!("div", {
+            .class("row")
+
+            .children(&mut [
+                html!("label", {
+                    .children(&mut [
+                        html!("input" => HtmlInputElement, {
+                            .attr("type", "checkbox")
+                            .prop_signal("checked", builtin.signal())
+
+                            .with_node!(element => {
+                                .event(clone!(app, builtin => move |_: events::Change| {
+                                    builtin.set_neq(element.checked());
+                                    app.uwuify();
+                                }))
+                            })
+                        }),
+
+                        html!("span", {
+                            .class("label-body")
+                            .text(&format!("Use the built-in {}", name.to_lowercase()))
+                        })
+                    ])
+                }),
+
+                html!("table", {
+                    .class("u-full-width")
+
+                    .children(&mut [
+                        html!("thead", {
+                            .child(html!("tr", {
+                                .children([name, "Weight", ""].map(|heading| {
+                                    html!("th", { .text(heading) })
+                                }))
+                            }))
+                        }),
+
+                        html!("tbody", {
+                            .children_signal_vec(list.signal_vec_cloned().map(clone!(app, list => move |row| {
+                                DecorationRow::render(row, list.clone(), app.clone())
+                            })))
+                        })
+                    ])
+                }),
+
+                html!("button", {
+                    .event(clone!(list => move |_: events::Click| {
+                        list.lock_mut().push_cloned(DecorationRow::new(&Decoration::custom("", 1)));
+                    }))
+
+                    .text(&format!("Add {one}"))
+                })
+            ])
+        })
+    }
+
     fn render_main(app: Arc<Self>) -> Dom {
         html!("div", {
             .class("container")
@@ -67,14 +685,21 @@ impl App {
                     .children(&mut [
                         html!("button", {
                             .style("margin-right", "5px")
-                            .style("width", "19%")
+                            .style("width", "16%")
                             .style("min-width", "min-content")
 
                             .event(clone!(app => move |_: events::Click| {
-                                app.uwuify.words.set_neq(1.0);
-                                app.uwuify.faces.set_neq(0.05);
-                                app.uwuify.actions.set_neq(0.125);
-                                app.uwuify.stutters.set_neq(0.225);
+                                app.words.set_neq(1.0);
+                                app.faces.set_neq(0.05);
+                                app.actions.set_neq(0.125);
+                                app.stutters.set_neq(0.225);
+                                app.sentence_faces.set_neq(0.0);
+                                app.sentence_actions.set_neq(0.0);
+                                app.passes.set_neq(1);
+                                app.cooldown.set_neq(0);
+                                app.burst.set_neq(1);
+                                app.max_per_sentence.set_neq(None);
+                                app.max_per_paragraph.set_neq(None);
                                 app.uwuify();
                             }))
 
@@ -83,14 +708,18 @@ impl App {
 
                         html!("button", {
                             .style("margin-right", "5px")
-                            .style("width", "19%")
+                            .style("width", "16%")
                             .style("min-width", "min-content")
 
                             .event(clone!(app => move |_: events::Click| {
-                                app.uwuify.words.set_neq(1.0);
-                                app.uwuify.faces.set_neq(1.0);
-                                app.uwuify.actions.set_neq(1.0);
-                                app.uwuify.stutters.set_neq(1.0);
+                                app.words.set_neq(1.0);
+                                app.faces.set_neq(1.0);
+                                app.actions.set_neq(1.0);
+                                app.stutters.set_neq(1.0);
+                                // Still chaotic, but with room to read in between
+                                app.cooldown.set_neq(1);
+                                app.burst.set_neq(2);
+                                app.max_per_sentence.set_neq(Some(4));
                                 app.uwuify();
                             }))
 
@@ -99,7 +728,7 @@ impl App {
 
                         html!("button", {
                             .style("margin-right", "5px")
-                            .style("width", "19%")
+                            .style("width", "16%")
                             .style("min-width", "min-content")
 
                             .event(clone!(app => move |_: events::Click| {
@@ -112,11 +741,23 @@ impl App {
 
                         html!("button", {
                             .style("margin-right", "5px")
-                            .style("width", "19%")
+                            .style("width", "16%")
                             .style("min-width", "min-content")
 
                             .event(clone!(app => move |_: events::Click| {
-                                app.uwuify.new_seed();
+                                app.deuwuify();
+                            }))
+
+                            .text("De-UwU")
+                        }),
+
+                        html!("button", {
+                            .style("margin-right", "5px")
+                            .style("width", "16%")
+                            .style("min-width", "min-content")
+
+                            .event(clone!(app => move |_: events::Click| {
+                                app.seed.set(random_seed().to_string());
                                 app.uwuify();
                             }))
 
@@ -125,7 +766,7 @@ impl App {
 
                         html!("button", {
                             .style("margin-right", "5px")
-                            .style("width", "19%")
+                            .style("width", "16%")
                             .style("min-width", "min-content")
 
                             .event(clone!(app => move |_: events::Click| {
@@ -133,7 +774,7 @@ impl App {
                                     if let Some(clipboard) = window.navigator().clipboard() {
                                         wasm_bindgen_futures::spawn_local(clone!(app => async move {
                                             wasm_bindgen_futures::JsFuture::from(
-                                                clipboard.write_text(&*app.output.lock_ref())
+                                                clipboard.write_text(&app.output.lock_ref())
                                             ).await.unwrap();
                                         }))
                                     }
@@ -160,7 +801,7 @@ impl App {
 
                                 html!("button", {
                                     .event(clone!(app => move |_: events::Click| {
-                                        app.uwuify.words.set_neq(1.0);
+                                        app.words.set_neq(1.0);
                                         app.uwuify();
                                     }))
 
@@ -175,11 +816,11 @@ impl App {
                                     .attr("max", "1")
                                     .attr("value", "1.0")
                                     .attr("step", "0.001")
-                                    .prop_signal("value", app.uwuify.words.signal())
+                                    .prop_signal("value", app.words.signal())
 
                                     .with_node!(element => {
                                         .event(clone!(app => move |_: events::Input| {
-                                            app.uwuify.words.set_neq(element.value_as_number());
+                                            app.words.set_neq(element.value_as_number());
                                             app.uwuify();
                                         }))
                                     })
@@ -198,7 +839,7 @@ impl App {
 
                                 html!("button", {
                                     .event(clone!(app => move |_: events::Click| {
-                                        app.uwuify.faces.set_neq(0.05);
+                                        app.faces.set_neq(0.05);
                                         app.uwuify();
                                     }))
 
@@ -213,11 +854,11 @@ impl App {
                                     .attr("max", "1")
                                     .attr("value", "0.05")
                                     .attr("step", "0.001")
-                                    .prop_signal("value", app.uwuify.faces.signal())
+                                    .prop_signal("value", app.faces.signal())
 
                                     .with_node!(element => {
                                         .event(clone!(app => move |_: events::Input| {
-                                            app.uwuify.faces.set_neq(element.value_as_number());
+                                            app.faces.set_neq(element.value_as_number());
                                             app.uwuify();
                                         }))
                                     })
@@ -236,7 +877,7 @@ impl App {
 
                                 html!("button", {
                                     .event(clone!(app => move |_: events::Click| {
-                                        app.uwuify.actions.set_neq(0.125);
+                                        app.actions.set_neq(0.125);
                                         app.uwuify();
                                     }))
 
@@ -251,11 +892,11 @@ impl App {
                                     .attr("max", "1")
                                     .attr("value", "0.125")
                                     .attr("step", "0.001")
-                                    .prop_signal("value", app.uwuify.actions.signal())
+                                    .prop_signal("value", app.actions.signal())
 
                                     .with_node!(element => {
                                         .event(clone!(app => move |_: events::Input| {
-                                            app.uwuify.actions.set_neq(element.value_as_number());
+                                            app.actions.set_neq(element.value_as_number());
                                             app.uwuify();
                                         }))
                                     })
@@ -274,7 +915,7 @@ impl App {
 
                                 html!("button", {
                                     .event(clone!(app => move |_: events::Click| {
-                                        app.uwuify.stutters.set_neq(0.225);
+                                        app.stutters.set_neq(0.225);
                                         app.uwuify();
                                     }))
 
@@ -289,11 +930,11 @@ impl App {
                                     .attr("max", "1")
                                     .attr("value", "0.225")
                                     .attr("step", "0.001")
-                                    .prop_signal("value", app.uwuify.stutters.signal())
+                                    .prop_signal("value", app.stutters.signal())
 
                                     .with_node!(element => {
                                         .event(clone!(app => move |_: events::Input| {
-                                            app.uwuify.stutters.set_neq(element.value_as_number());
+                                            app.stutters.set_neq(element.value_as_number());
                                             app.uwuify();
                                         }))
                                     })
@@ -303,12 +944,740 @@ impl App {
                     ])
                 }),
 
-                html!("p", {
-                    .attr("style", "min-height:200px")
-                    .text_signal(app.output.signal_cloned())
-                })
-            ])
-        })
+                html!("div", {
+                    .class("row")
+
+                    .children(&mut [
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "sentence_faces")
+                                    .text("Faces after sentences and commas")
+                                }),
+
+                                html!("button", {
+                                    .event(clone!(app => move |_: events::Click| {
+                                        app.sentence_faces.set_neq(0.0);
+                                        app.uwuify();
+                                    }))
+
+                                    .text("Reset")
+                                }),
+
+                                html!("input" => HtmlInputElement, {
+                                    .class("u-full-width")
+                                    .attr("type", "range")
+                                    .attr("id", "sentence_faces")
+                                    .attr("min", "0")
+                                    .attr("max", "1")
+                                    .attr("value", "0.0")
+                                    .attr("step", "0.001")
+                                    .prop_signal("value", app.sentence_faces.signal())
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Input| {
+                                            app.sentence_faces.set_neq(element.value_as_number());
+                                            app.uwuify();
+                                        }))
+                                    })
+                                })
+                            ])
+                        }),
+
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "sentence_actions")
+                                    .text("Actions between sentences")
+                                }),
+
+                                html!("button", {
+                                    .event(clone!(app => move |_: events::Click| {
+                                        app.sentence_actions.set_neq(0.0);
+                                        app.uwuify();
+                                    }))
+
+                                    .text("Reset")
+                                }),
+
+                                html!("input" => HtmlInputElement, {
+                                    .class("u-full-width")
+                                    .attr("type", "range")
+                                    .attr("id", "sentence_actions")
+                                    .attr("min", "0")
+                                    .attr("max", "1")
+                                    .attr("value", "0.0")
+                                    .attr("step", "0.001")
+                                    .prop_signal("value", app.sentence_actions.signal())
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Input| {
+                                            app.sentence_actions.set_neq(element.value_as_number());
+                                            app.uwuify();
+                                        }))
+                                    })
+                                })
+                            ])
+                        }),
+
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "passes")
+                                    .text_signal(app.passes.signal_ref(|passes| format!("Passes: {passes}")))
+                                }),
+
+                                html!("button", {
+                                    .event(clone!(app => move |_: events::Click| {
+                                        app.passes.set_neq(1);
+                                        app.uwuify();
+                                    }))
+
+                                    .text("Reset")
+                                }),
+
+                                html!("input" => HtmlInputElement, {
+                                    .class("u-full-width")
+                                    .attr("type", "range")
+                                    .attr("id", "passes")
+                                    .attr("min", "1")
+                                    .attr("max", "5")
+                                    .attr("value", "1")
+                                    .attr("step", "1")
+                                    .prop_signal("value", app.passes.signal_ref(|passes| f64::from(*passes)))
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Input| {
+                                            app.passes.set_neq(element.value_as_number() as u32);
+                                            app.uwuify();
+                                        }))
+                                    })
+                                })
+                            ])
+                        })
+                    ])
+                }),
+
+                html!("div", {
+                    .class("row")
+
+                    .children(&mut [
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "cooldown")
+                                    .text_signal(app.cooldown.signal_ref(|cooldown| format!("Words between faces: {cooldown}")))
+                                }),
+
+                                html!("button", {
+                                    .event(clone!(app => move |_: events::Click| {
+                                        app.cooldown.set_neq(0);
+                                        app.uwuify();
+                                    }))
+
+                                    .text("Reset")
+                                }),
+
+                                html!("input" => HtmlInputElement, {
+                                    .class("u-full-width")
+                                    .attr("type", "range")
+                                    .attr("id", "cooldown")
+                                    .attr("min", "0")
+                                    .attr("max", "10")
+                                    .attr("value", "0")
+                                    .attr("step", "1")
+                                    .prop_signal("value", app.cooldown.signal_ref(|cooldown| f64::from(*cooldown)))
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Input| {
+                                            app.cooldown.set_neq(element.value_as_number() as u32);
+                                            app.uwuify();
+                                        }))
+                                    })
+                                })
+                            ])
+                        }),
+
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "burst")
+                                    .text_signal(app.burst.signal_ref(|burst| format!("Faces in a row: {burst}")))
+                                }),
+
+                                html!("button", {
+                                    .event(clone!(app => move |_: events::Click| {
+                                        app.burst.set_neq(1);
+                                        app.uwuify();
+                                    }))
+
+                                    .text("Reset")
+                                }),
+
+                                html!("input" => HtmlInputElement, {
+                                    .class("u-full-width")
+                                    .attr("type", "range")
+                                    .attr("id", "burst")
+                                    .attr("min", "1")
+                                    .attr("max", "5")
+                                    .attr("value", "1")
+                                    .attr("step", "1")
+                                    .prop_signal("value", app.burst.signal_ref(|burst| f64::from(*burst)))
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Input| {
+                                            app.burst.set_neq(element.value_as_number() as u32);
+                                            app.uwuify();
+                                        }))
+                                    })
+                                })
+                            ])
+                        }),
+
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "max_per_sentence")
+                                    .text_signal(app.max_per_sentence.signal_ref(|max_per_sentence| match max_per_sentence {
+                                        Some(max_per_sentence) => format!("Per sentence: {max_per_sentence}"),
+                                        None => "Per sentence: no limit".to_owned(),
+                                    }))
+                                }),
+
+                                html!("button", {
+                                    .event(clone!(app => move |_: events::Click| {
+                                        app.max_per_sentence.set_neq(None);
+                                        app.uwuify();
+                                    }))
+
+                                    .text("Reset")
+                                }),
+
+                                // All the way to the right is no limit
+                                html!("input" => HtmlInputElement, {
+                                    .class("u-full-width")
+                                    .attr("type", "range")
+                                    .attr("id", "max_per_sentence")
+                                    .attr("min", "0")
+                                    .attr("max", "10")
+                                    .attr("value", "10")
+                                    .attr("step", "1")
+                                    .prop_signal("value", app.max_per_sentence.signal_ref(|max_per_sentence| f64::from(max_per_sentence.unwrap_or(10))))
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Input| {
+                                            let max_per_sentence = element.value_as_number() as u32;
+                                            app.max_per_sentence.set_neq((max_per_sentence < 10).then_some(max_per_sentence));
+                                            app.uwuify();
+                                        }))
+                                    })
+                                })
+                            ])
+                        }),
+
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "max_per_paragraph")
+                                    .text_signal(app.max_per_paragraph.signal_ref(|max_per_paragraph| match max_per_paragraph {
+                                        Some(max_per_paragraph) => format!("Per paragraph: {max_per_paragraph}"),
+                                        None => "Per paragraph: no limit".to_owned(),
+                                    }))
+                                }),
+
+                                html!("button", {
+                                    .event(clone!(app => move |_: events::Click| {
+                                        app.max_per_paragraph.set_neq(None);
+                                        app.uwuify();
+                                    }))
+
+                                    .text("Reset")
+                                }),
+
+                                // All the way to the right is no limit
+                                html!("input" => HtmlInputElement, {
+                                    .class("u-full-width")
+                                    .attr("type", "range")
+                                    .attr("id", "max_per_paragraph")
+                                    .attr("min", "0")
+                                    .attr("max", "20")
+                                    .attr("value", "20")
+                                    .attr("step", "1")
+                                    .prop_signal("value", app.max_per_paragraph.signal_ref(|max_per_paragraph| f64::from(max_per_paragraph.unwrap_or(20))))
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Input| {
+                                            let max_per_paragraph = element.value_as_number() as u32;
+                                            app.max_per_paragraph.set_neq((max_per_paragraph < 20).then_some(max_per_paragraph));
+                                            app.uwuify();
+                                        }))
+                                    })
+                                })
+                            ])
+                        })
+                    ])
+                }),
+
+                html!("div", {
+                    .class("row")
+
+                    .children(Category::ALL.iter().copied().map(|category| {
+                        html!("label", {
+                            .style("display", "inline-block")
+                            .style("margin-right", "15px")
+
+                            .children(&mut [
+                                html!("input" => HtmlInputElement, {
+                                    .attr("type", "checkbox")
+                                    .prop_signal("checked", app.categories.signal_ref(move |categories| categories.contains(&category)))
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Change| {
+                                            let mut categories = app.categories.lock_mut();
+                                            if element.checked() {
+                                                categories.push(category);
+                                                categories.sort();
+                                            } else {
+                                                categories.retain(|other| *other != category);
+                                            }
+                                            drop(categories);
+                                            app.uwuify();
+                                        }))
+                                    })
+                                }),
+
+                                html!("span", {
+                                    .class("label-body")
+                                    .text(match category {
+                                        Category::Happy => "Happy",
+                                        Category::Sad => "Sad",
+                                        Category::Angry => "Angry",
+                                        Category::Cat => "Cat",
+                                        Category::Shy => "Shy",
+                                        Category::Nsfw => "NSFW",
+                                    })
+                                })
+                            ])
+                        })
+                    }))
+
+                    .child(html!("label", {
+                        .style("display", "inline-block")
+
+                        .children(&mut [
+                            html!("input" => HtmlInputElement, {
+                                .attr("type", "checkbox")
+                                .prop_signal("checked", app.sfw.signal())
+
+                                .with_node!(element => {
+                                    .event(clone!(app => move |_: events::Change| {
+                                        app.sfw.set_neq(element.checked());
+                                        app.uwuify();
+                                    }))
+                                })
+                            }),
+
+                            html!("span", {
+                                .class("label-body")
+                                .text("Safe for work")
+                            })
+                        ])
+                    }))
+
+                    .child(html!("label", {
+                        .style("display", "inline-block")
+                        .style("margin-left", "15px")
+
+                        .children(&mut [
+                            html!("input" => HtmlInputElement, {
+                                .attr("type", "checkbox")
+                                .prop_signal("checked", app.sentiment.signal())
+
+                                .with_node!(element => {
+                                    .event(clone!(app => move |_: events::Change| {
+                                        app.sentiment.set_neq(element.checked());
+                                        app.uwuify();
+                                    }))
+                                })
+                            }),
+
+                            html!("span", {
+                                .class("label-body")
+                                .text("Match the mood of each sentence")
+                            })
+                        ])
+                    }))
+                }),
+
+                html!("div", {
+                    .class("row")
+
+                    .children(&mut [
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "mode")
+                                    .text("Input format")
+                                }),
+
+                                html!("select" => HtmlSelectElement, {
+                                    .class("u-full-width")
+                                    .attr("id", "mode")
+
+                                    .children(InputMode::ALL.iter().copied().map(|mode| {
+                                        html!("option", {
+                                            .prop_signal("selected", app.mode.signal_ref(move |selected| *selected == mode))
+                                            .text(mode.name())
+                                        })
+                                    }))
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Change| {
+                                            if let Some(mode) = InputMode::ALL.get(element.selected_index() as usize) {
+                                                app.mode.set_neq(*mode);
+                                                app.uwuify();
+                                            }
+                                        }))
+                                    })
+                                })
+                            ])
+                        }),
+
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "seed")
+                                    .text("Seed")
+                                }),
+
+                                html!("input" => HtmlInputElement, {
+                                    .class("u-full-width")
+                                    .attr("type", "text")
+                                    .attr("id", "seed")
+                                    .attr("placeholder", "A number or any text")
+                                    .prop_signal("value", app.seed.signal_cloned())
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Input| {
+                                            app.seed.set_neq(element.value());
+                                            app.uwuify();
+                                        }))
+                                    })
+                                })
+                            ])
+                        }),
+
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "seeding")
+                                    .text("Vary words by")
+                                }),
+
+                                html!("select" => HtmlSelectElement, {
+                                    .class("u-full-width")
+                                    .attr("id", "seeding")
+
+                                    .children(Seeding::ALL.iter().copied().map(|seeding| {
+                                        html!("option", {
+                                            .prop_signal("selected", app.seeding.signal_ref(move |selected| *selected == seeding))
+                                            .text(match seeding {
+                                                Seeding::Word => "Nothing",
+                                                Seeding::Position => "Position",
+                                                Seeding::Context => "Neighbouring words",
+                                            })
+                                        })
+                                    }))
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Change| {
+                                            if let Some(seeding) = Seeding::ALL.get(element.selected_index() as usize) {
+                                                app.seeding.set_neq(*seeding);
+                                                app.uwuify();
+                                            }
+                                        }))
+                                    })
+                                })
+                            ])
+                        }),
+
+                        html!("div", {
+                            .class(["three", "columns"])
+
+                            .children(&mut [
+                                html!("label", {
+                                    .attr("for", "preset")
+                                    .text("Preset")
+                                }),
+
+                                html!("button", {
+                                    .style("margin-right", "5px")
+
+                                    .event(clone!(app => move |_: events::Click| {
+                                        app.export_preset();
+                                    }))
+
+                                    .text("Export")
+                                }),
+
+                                html!("input" => HtmlInputElement, {
+                                    .attr("type", "file")
+                                    .attr("id", "preset")
+                                    .attr("accept", ".json,.toml")
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Change| {
+                                            if let Some(file) = element.files().and_then(|files| files.get(0)) {
+                                                wasm_bindgen_futures::spawn_local(clone!(app => async move {
+                                                    app.import_preset(file).await;
+                                                }))
+                                            }
+                                        }))
+                                    })
+                                })
+                            ])
+                        })
+                    ])
+                }),
+
+                html!("div", {
+                    .class("row")
+
+                    .child(html!("div", {
+                        .class(["three", "columns"])
+
+                        .children(&mut [
+                            html!("label", {
+                                .attr("for", "language")
+                                .text("Language")
+                            }),
+
+                            html!("select" => HtmlSelectElement, {
+                                .class("u-full-width")
+                                .attr("id", "language")
+
+                                .children(Language::ALL.iter().copied().map(|language| {
+                                    html!("option", {
+                                        .prop_signal("selected", app.language.signal_ref(move |selected| *selected == language))
+                                        .text(match language {
+                                            Language::Auto => "Detect from the script",
+                                            Language::English => "English",
+                                            Language::Spanish => "Español",
+                                            Language::German => "Deutsch",
+                                            Language::Russian => "Русский",
+                                            Language::Japanese => "日本語",
+                                        })
+                                    })
+                                }))
+
+                                .with_node!(element => {
+                                    .event(clone!(app => move |_: events::Change| {
+                                        if let Some(language) = Language::ALL.get(element.selected_index() as usize) {
+                                            app.language.set_neq(*language);
+                                            app.uwuify();
+                                        }
+                                    }))
+                                })
+                            })
+                        ])
+                    }))
+
+                    .child(html!("div", {
+                        .class(["three", "columns"])
+
+                        .children(&mut [
+                            html!("label", {
+                                .attr("for", "segmentation")
+                                .text("Split words at")
+                            }),
+
+                            html!("select" => HtmlSelectElement, {
+                                .class("u-full-width")
+                                .attr("id", "segmentation")
+                                .attr("title", "Thai, Lao, Khmer and Burmese need a dictionary to find their words, so they're only split at spaces")
+
+                                .children(Segmentation::ALL.iter().copied().map(|segmentation| {
+                                    html!("option", {
+                                        .prop_signal("selected", app.segmentation.signal_ref(move |selected| *selected == segmentation))
+                                        .text(match segmentation {
+                                            Segmentation::Spaces => "Spaces",
+                                            Segmentation::Unicode => "Word boundaries (中文, 日本語)",
+                                        })
+                                    })
+                                }))
+
+                                .with_node!(element => {
+                                    .event(clone!(app => move |_: events::Change| {
+                                        if let Some(segmentation) = Segmentation::ALL.get(element.selected_index() as usize) {
+                                            app.segmentation.set_neq(*segmentation);
+                                            app.uwuify();
+                                        }
+                                    }))
+                                })
+                            })
+                        ])
+                    }))
+                }),
+
+                html!("div", {
+                    .class("row")
+
+                    .children(&mut [
+                        html!("label", {
+                            .children(&mut [
+                                html!("input" => HtmlInputElement, {
+                                    .attr("type", "checkbox")
+                                    .prop_signal("checked", app.builtin_rules.signal())
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Change| {
+                                            app.builtin_rules.set_neq(element.checked());
+                                            app.uwuify();
+                                        }))
+                                    })
+                                }),
+
+                                html!("span", {
+                                    .class("label-body")
+                                    .text("Use the built-in rules")
+                                })
+                            ])
+                        }),
+
+                        html!("table", {
+                            .class("u-full-width")
+
+                            .children(&mut [
+                                html!("thead", {
+                                    .child(html!("tr", {
+                                        .children(["Find", "Replace", "Pattern", "Anchor", "Case", "Priority", ""].map(|heading| {
+                                            html!("th", { .text(heading) })
+                                        }))
+                                    }))
+                                }),
+
+                                html!("tbody", {
+                                    .children_signal_vec(app.rules.signal_vec_cloned().map(clone!(app => move |rule| {
+                                        RuleRow::render(rule, app.clone())
+                                    })))
+                                })
+                            ])
+                        }),
+
+                        html!("button", {
+                            .event(clone!(app => move |_: events::Click| {
+                                app.rules.lock_mut().push_cloned(RuleRow::new(&Rule::literal("", "")));
+                            }))
+
+                            .text("Add rule")
+                        })
+                    ])
+                }),
+
+                html!("div", {
+                    .class("row")
+
+                    .children(&mut [
+                        html!("label", {
+                            .children(&mut [
+                                html!("input" => HtmlInputElement, {
+                                    .attr("type", "checkbox")
+                                    .prop_signal("checked", app.builtin_dictionary.signal())
+
+                                    .with_node!(element => {
+                                        .event(clone!(app => move |_: events::Change| {
+                                            app.builtin_dictionary.set_neq(element.checked());
+                                            app.uwuify();
+                                        }))
+                                    })
+                                }),
+
+                                html!("span", {
+                                    .class("label-body")
+                                    .text("Use the built-in dictionary")
+                                })
+                            ])
+                        }),
+
+                        html!("table", {
+                            .class("u-full-width")
+
+                            .children(&mut [
+                                html!("thead", {
+                                    .child(html!("tr", {
+                                        .children(["Word", "Replacement", ""].map(|heading| {
+                                            html!("th", { .text(heading) })
+                                        }))
+                                    }))
+                                }),
+
+                                html!("tbody", {
+                                    .children_signal_vec(app.dictionary.signal_vec_cloned().map(clone!(app => move |row| {
+                                        DictionaryRow::render(row, app.clone())
+                                    })))
+                                })
+                            ])
+                        }),
+
+                        html!("button", {
+                            .event(clone!(app => move |_: events::Click| {
+                                app.dictionary.lock_mut().push_cloned(DictionaryRow::new("", ""));
+                            }))
+
+                            .text("Add word")
+                        })
+                    ])
+                }),
+
+                App::render_decorations(&app, "Faces", "face", &app.builtin_faces, &app.custom_faces),
+                App::render_decorations(&app, "Actions", "action", &app.builtin_actions, &app.custom_actions),
+
+                html!("p", {
+                    .attr("style", "min-height:200px;white-space:pre-wrap")
+                    .text_signal(app.output.signal_cloned())
+                })
+            ])
+        })
+    }
+}
+
+/// Where the custom faces and actions are kept between visits, as a preset with only those
+const DECORATIONS_KEY: &str = "webuwu.decorations";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+fn save_decorations(config: &UwUConfig) {
+    let saved = UwUConfig::builder()
+        .builtin_faces(config.builtin_faces())
+        .custom_faces(config.custom_faces().to_vec())
+        .builtin_actions(config.builtin_actions())
+        .custom_actions(config.custom_actions().to_vec())
+        .build();
+
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(DECORATIONS_KEY, &saved.to_preset(PresetFormat::Json));
     }
 }
 